@@ -14,17 +14,25 @@
 //!         members: &[
 //!             MemberInfo {
 //!                 name: "name",
-//!                 r#type: <String as IntoPy<PyObject>>::type_output,
+//!                 r#type: <String as ::pyo3_stub_gen::PyStubType>::type_output,
 //!             },
 //!             MemberInfo {
 //!                 name: "ndim",
-//!                 r#type: <usize as IntoPy<PyObject>>::type_output,
+//!                 r#type: <usize as ::pyo3_stub_gen::PyStubType>::type_output,
 //!             },
 //!             MemberInfo {
 //!                 name: "description",
-//!                 r#type: <Option<String> as IntoPy<PyObject>>::type_output,
+//!                 r#type: <Option<String> as ::pyo3_stub_gen::PyStubType>::type_output,
 //!             },
 //!         ],
+//!         setters: &[],
+//!         base: None,
+//!         subclass: false,
+//!         eq: false,
+//!         ord: false,
+//!         hash: false,
+//!         str: false,
+//!         frozen: false,
 //!         doc: "",
 //!     }
 //! }
@@ -69,6 +77,7 @@ mod pyclass_enum;
 mod pyfunction;
 mod pymethods;
 mod signature;
+mod stub_type;
 mod util;
 
 use arg::*;
@@ -81,16 +90,23 @@ use pyclass_enum::*;
 use pyfunction::*;
 use pymethods::*;
 use signature::*;
+use stub_type::*;
 use util::*;
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse2, ItemEnum, ItemFn, ItemImpl, ItemStruct, Result};
+use syn::{parse2, ImplItem, ItemEnum, ItemFn, ItemImpl, ItemStruct, Result};
 
 pub fn pyclass(item: TokenStream2) -> Result<TokenStream2> {
-    let inner = PyClassInfo::try_from(parse2::<ItemStruct>(item.clone())?)?;
+    let mut item_struct = parse2::<ItemStruct>(item)?;
+    let inner = PyClassInfo::try_from(item_struct.clone())?;
+    let derive_stub_type = StubType::from(&inner);
+    for field in item_struct.fields.iter_mut() {
+        prune_attrs(&mut field.attrs);
+    }
     Ok(quote! {
-        #item
+        #item_struct
+        #derive_stub_type
         pyo3_stub_gen::inventory::submit! {
             #inner
         }
@@ -98,9 +114,19 @@ pub fn pyclass(item: TokenStream2) -> Result<TokenStream2> {
 }
 
 pub fn pyclass_enum(item: TokenStream2) -> Result<TokenStream2> {
-    let inner = PyEnumInfo::try_from(parse2::<ItemEnum>(item.clone())?)?;
+    let mut item_enum = parse2::<ItemEnum>(item)?;
+    let inner = PyEnumInfo::try_from(item_enum.clone())?;
+    let derive_stub_type = StubType::from(&inner);
+    let derive_pyclass_enum = PyClassEnumImpl::from(&inner);
+    for variant in item_enum.variants.iter_mut() {
+        for field in variant.fields.iter_mut() {
+            prune_attrs(&mut field.attrs);
+        }
+    }
     Ok(quote! {
-        #item
+        #item_enum
+        #derive_stub_type
+        #derive_pyclass_enum
         pyo3_stub_gen::inventory::submit! {
             #inner
         }
@@ -108,9 +134,17 @@ pub fn pyclass_enum(item: TokenStream2) -> Result<TokenStream2> {
 }
 
 pub fn pymethods(item: TokenStream2) -> Result<TokenStream2> {
-    let inner = PyMethodsInfo::try_from(parse2::<ItemImpl>(item.clone())?)?;
+    let mut item_impl = parse2::<ItemImpl>(item)?;
+    let inner = PyMethodsInfo::try_from(item_impl.clone())?;
+    for item in item_impl.items.iter_mut() {
+        match item {
+            ImplItem::Fn(method) => prune_fn_attrs(&mut method.attrs, &mut method.sig),
+            ImplItem::Const(item_const) => prune_attrs(&mut item_const.attrs),
+            _ => {}
+        }
+    }
     Ok(quote! {
-        #item
+        #item_impl
         pyo3_stub_gen::inventory::submit! {
             #inner
         }
@@ -118,10 +152,12 @@ pub fn pymethods(item: TokenStream2) -> Result<TokenStream2> {
 }
 
 pub fn pyfunction(attr: TokenStream2, item: TokenStream2) -> Result<TokenStream2> {
-    let mut inner = PyFunctionInfo::try_from(parse2::<ItemFn>(item.clone())?)?;
+    let mut item_fn = parse2::<ItemFn>(item)?;
+    let mut inner = PyFunctionInfo::try_from(item_fn.clone())?;
     inner.parse_attr(attr)?;
+    prune_fn_attrs(&mut item_fn.attrs, &mut item_fn.sig);
     Ok(quote! {
-        #item
+        #item_fn
         pyo3_stub_gen::inventory::submit! {
             #inner
         }
@@ -1,8 +1,10 @@
+use super::{parse_override_type, remove_lifetime, OverrideType, Signature};
+
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
-    spanned::Spanned, FnArg, GenericArgument, PatType, PathArguments, Result, Type, TypePath,
-    TypeReference,
+    ext::IdentExt, spanned::Spanned, FnArg, GenericArgument, PatType, PathArguments, Result, Type,
+    TypePath,
 };
 
 pub fn parse_args(iter: impl IntoIterator<Item = FnArg>) -> Result<Vec<ArgInfo>> {
@@ -36,76 +38,96 @@ pub fn parse_args(iter: impl IntoIterator<Item = FnArg>) -> Result<Vec<ArgInfo>>
     Ok(args)
 }
 
-#[derive(Debug)]
+/// Mark arguments captured by `*args` and `**kwargs` in `#[pyo3(signature = (...))]`.
+///
+/// Their Rust types, `&PyTuple` and `Option<&PyDict>`, describe the container,
+/// while Python annotates each element of them.
+pub fn mark_variadic_args(args: &mut [ArgInfo], sig: &Signature) {
+    let names = sig.variadic_names();
+    for arg in args {
+        if names.contains(&arg.name) {
+            arg.variadic = true;
+        }
+    }
+}
+
+/// Mark trailing `Option<T>` arguments, which PyO3 makes optional with default `None`
+/// when `#[pyo3(signature = (...))]` is not given.
+pub fn mark_optional_args(args: &mut [ArgInfo]) {
+    for arg in args.iter_mut().rev() {
+        if !is_option(&arg.r#type) {
+            break;
+        }
+        arg.optional = true;
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) => path
+            .segments
+            .last()
+            .is_some_and(|last| last.ident == "Option"),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ArgInfo {
     name: String,
     pub r#type: Type,
+    /// Python type specified by `#[gen_stub(override_type(...))]`
+    pub override_type: Option<OverrideType>,
+    /// Captured by `*args` or `**kwargs`
+    variadic: bool,
+    /// Trailing `Option<T>` argument defaults to `None`
+    optional: bool,
 }
 
 impl TryFrom<FnArg> for ArgInfo {
     type Error = syn::Error;
     fn try_from(value: FnArg) -> Result<Self> {
         let span = value.span();
-        if let FnArg::Typed(PatType { pat, ty, .. }) = value {
-            if let syn::Pat::Ident(mut ident) = *pat {
-                ident.mutability = None;
-                let name = ident.to_token_stream().to_string();
-                return Ok(Self { name, r#type: *ty });
+        if let FnArg::Typed(PatType { pat, ty, attrs, .. }) = value {
+            if let syn::Pat::Ident(ident) = *pat {
+                let name = ident.ident.unraw().to_string();
+                let mut ty = *ty;
+                remove_lifetime(&mut ty);
+                return Ok(Self {
+                    name,
+                    r#type: ty,
+                    override_type: parse_override_type(&attrs)?,
+                    variadic: false,
+                    optional: false,
+                });
             }
         }
         Err(syn::Error::new(span, "Expected typed argument"))
     }
 }
 
-fn type_to_token(ty: &Type) -> TokenStream2 {
-    match ty {
-        Type::Path(TypePath { path, .. }) => {
-            if let Some(last_seg) = path.segments.last() {
-                // `CompareOp` is an enum for `__richcmp__`
-                // PyO3 reference: https://docs.rs/pyo3/latest/pyo3/pyclass/enum.CompareOp.html
-                // PEP: https://peps.python.org/pep-0207/
-                if last_seg.ident == "CompareOp" {
-                    quote! { ::pyo3_stub_gen::type_info::compare_op_type_input }
-                } else {
-                    quote! { <#ty as FromPyObject>::type_input }
-                }
-            } else {
-                unreachable!("Empty path segment: {:?}", path);
-            }
-        }
-        Type::Reference(TypeReference { elem, .. }) => {
-            match elem.as_ref() {
-                Type::Path(TypePath { path, .. }) => {
-                    if let Some(last) = path.segments.last() {
-                        match last.ident.to_string().as_str() {
-                            // Types where `&T: FromPyObject` instead of `T: FromPyObject`
-                            // i.e. `&str` and most of `Py*` types defined in PyO3.
-                            "str" | "PyAny" | "PyString" | "PyDict" => {
-                                return quote! { <#ty as FromPyObject>::type_input };
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                Type::Slice(_) => {
-                    return quote! { <#ty as FromPyObject>::type_input };
-                }
-                _ => {}
-            }
-            type_to_token(elem)
-        }
-        _ => {
-            quote! { <#ty as FromPyObject>::type_input }
-        }
-    }
-}
-
 impl ToTokens for ArgInfo {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let Self { name, r#type: ty } = self;
-        let type_tt = type_to_token(ty);
+        let Self {
+            name,
+            r#type: ty,
+            override_type,
+            variadic,
+            optional,
+        } = self;
+        let type_tt = if let Some(override_type) = override_type {
+            quote! { #override_type }
+        } else if *variadic {
+            quote! { ::pyo3_stub_gen::TypeInfo::any }
+        } else {
+            quote! { <#ty as ::pyo3_stub_gen::PyStubType>::type_input }
+        };
         tokens.append_all(quote! {
-            ::pyo3_stub_gen::type_info::ArgInfo { name: #name, r#type: #type_tt }
+            ::pyo3_stub_gen::type_info::ArgInfo {
+                name: #name,
+                r#type: #type_tt,
+                is_optional: #optional,
+            }
         });
     }
 }
@@ -1,7 +1,10 @@
 use super::Signature;
-use proc_macro2::TokenTree;
-use quote::ToTokens;
-use syn::{Attribute, Expr, ExprLit, Ident, Lit, Meta, MetaList, Result};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::{
+    meta::ParseNestedMeta, parenthesized, punctuated::Punctuated, Attribute, Error, Expr, ExprLit,
+    Ident, Lit, LitStr, Meta, MetaList, Result, Token, Type,
+};
 
 pub fn extract_documents(attrs: &[Attribute]) -> Vec<String> {
     let mut docs = Vec::new();
@@ -52,15 +55,26 @@ pub enum Attr {
     Name(String),
     Get,
     GetAll,
+    Set,
+    SetAll,
     Module(String),
     Signature(Signature),
+    Extends(Type),
+    Subclass,
+    Eq,
+    Ord,
+    Hash,
+    Str,
+    Frozen,
 
     // Attributes appears in components within `#[pymethods]`
     // <https://docs.rs/pyo3/latest/pyo3/attr.pymethods.html>
     New,
     Getter(Option<String>),
+    Setter(Option<String>),
     StaticMethod,
     ClassMethod,
+    Classattr,
 }
 
 pub fn parse_pyo3_attrs(attrs: &[Attribute]) -> Result<Vec<Attr>> {
@@ -104,6 +118,30 @@ pub fn parse_pyo3_attr(attr: &Attribute) -> Result<Vec<Attr>> {
                         if ident == "get_all" {
                             pyo3_attrs.push(Attr::GetAll);
                         }
+                        if ident == "set" {
+                            pyo3_attrs.push(Attr::Set);
+                        }
+                        if ident == "set_all" {
+                            pyo3_attrs.push(Attr::SetAll);
+                        }
+                        if ident == "subclass" {
+                            pyo3_attrs.push(Attr::Subclass);
+                        }
+                        if ident == "eq" {
+                            pyo3_attrs.push(Attr::Eq);
+                        }
+                        if ident == "ord" {
+                            pyo3_attrs.push(Attr::Ord);
+                        }
+                        if ident == "hash" {
+                            pyo3_attrs.push(Attr::Hash);
+                        }
+                        if ident == "str" {
+                            pyo3_attrs.push(Attr::Str);
+                        }
+                        if ident == "frozen" {
+                            pyo3_attrs.push(Attr::Frozen);
+                        }
                     }
                     [Ident(ident), Punct(_), Literal(lit)] => {
                         if ident == "name" {
@@ -114,12 +152,18 @@ pub fn parse_pyo3_attr(attr: &Attribute) -> Result<Vec<Attr>> {
                             pyo3_attrs
                                 .push(Attr::Module(lit.to_string().trim_matches('"').to_string()));
                         }
-                    }
-                    [Ident(ident), Punct(_), Group(group)] => {
-                        if ident == "signature" {
-                            pyo3_attrs.push(Attr::Signature(syn::parse2(group.to_token_stream())?));
+                        // `#[pyclass(str = "{x}")]` of PyO3 0.23+ with a format string
+                        if ident == "str" {
+                            pyo3_attrs.push(Attr::Str);
                         }
                     }
+                    [Ident(ident), Punct(_), Group(group)] if ident == "signature" => {
+                        pyo3_attrs.push(Attr::Signature(syn::parse2(group.to_token_stream())?));
+                    }
+                    [Ident(ident), Punct(_), base @ ..] if ident == "extends" => {
+                        let base = base.iter().cloned().collect::<TokenStream2>();
+                        pyo3_attrs.push(Attr::Extends(syn::parse2(base)?));
+                    }
                     _ => {}
                 }
             }
@@ -130,21 +174,287 @@ pub fn parse_pyo3_attr(attr: &Attribute) -> Result<Vec<Attr>> {
         pyo3_attrs.push(Attr::StaticMethod);
     } else if path.is_ident("classmethod") {
         pyo3_attrs.push(Attr::ClassMethod);
+    } else if path.is_ident("classattr") {
+        pyo3_attrs.push(Attr::Classattr);
     } else if path.is_ident("getter") {
         if let Ok(inner) = attr.parse_args::<Ident>() {
             pyo3_attrs.push(Attr::Getter(Some(inner.to_string())));
         } else {
             pyo3_attrs.push(Attr::Getter(None));
         }
+    } else if path.is_ident("setter") {
+        if let Ok(inner) = attr.parse_args::<Ident>() {
+            pyo3_attrs.push(Attr::Setter(Some(inner.to_string())));
+        } else {
+            pyo3_attrs.push(Attr::Setter(None));
+        }
     }
 
     Ok(pyo3_attrs)
 }
 
+/// `#[gen_stub(...)]` style attributes consumed only by this crate
+///
+/// These attributes are removed from the item before PyO3 macros see it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StubGenAttr {
+    /// `#[gen_stub(override_type(...))]` on arguments and `#[pyo3(get)]` fields
+    OverrideType(OverrideType),
+    /// `#[gen_stub(override_return_type(...))]` on functions, methods and getters
+    OverrideReturnType(OverrideType),
+    /// `#[gen_stub(richcmp(eq, ne))]` on `__richcmp__` to restrict the comparison operators in stub
+    Richcmp(Vec<String>),
+    /// `#[gen_stub(awaitable(T))]` on functions returning a Python awaitable, e.g. by `future_into_py`,
+    /// whose result is `T`
+    Awaitable(Type),
+    /// `#[gen_stub(overload = "def f(x: int) -> int")]` or
+    /// `#[gen_stub(overload(signature = "...", imports = (...)))]` on functions and methods, can be repeated
+    Overload(Overload),
+}
+
+/// Python signature specified by `#[gen_stub(overload(...))]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overload {
+    pub signature: LitStr,
+    /// Modules to be imported as `import {module}` for using types in `signature`
+    pub imports: Vec<String>,
+}
+
+impl Overload {
+    fn parse_nested(meta: ParseNestedMeta) -> Result<Self> {
+        if meta.input.peek(Token![=]) {
+            return Ok(Self {
+                signature: meta.value()?.parse()?,
+                imports: Vec::new(),
+            });
+        }
+        let mut signature = None;
+        let mut imports = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("signature") {
+                signature = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("imports") {
+                imports.extend(parse_imports(&meta)?);
+            } else {
+                return Err(meta.error("Expected `signature` or `imports`"));
+            }
+            Ok(())
+        })?;
+        let signature = signature.ok_or_else(|| meta.error("Missing `signature`"))?;
+        Ok(Self { signature, imports })
+    }
+}
+
+impl ToTokens for Overload {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let signature = self.signature.value();
+        let imports = &self.imports;
+        tokens.append_all(quote! {
+            ::pyo3_stub_gen::type_info::OverloadInfo {
+                signature: #signature,
+                imports: &[ #(#imports),* ],
+            }
+        })
+    }
+}
+
+/// Parse `imports = ("module", ...)`
+fn parse_imports(meta: &ParseNestedMeta) -> Result<Vec<String>> {
+    let value = meta.value()?;
+    let content;
+    parenthesized!(content in value);
+    let modules = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    Ok(modules.iter().map(LitStr::value).collect())
+}
+
+/// Python type literally specified by
+/// `override_type(type_repr = "typing.SupportsFloat", imports = ("typing",))`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverrideType {
+    pub type_repr: String,
+    /// Modules to be imported as `import {module}` for using `type_repr`
+    pub imports: Vec<String>,
+}
+
+impl OverrideType {
+    fn parse_nested(meta: ParseNestedMeta) -> Result<Self> {
+        let mut type_repr = None;
+        let mut imports = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type_repr") {
+                type_repr = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("imports") {
+                imports.extend(parse_imports(&meta)?);
+            } else {
+                return Err(meta.error("Expected `type_repr` or `imports`"));
+            }
+            Ok(())
+        })?;
+        let type_repr = type_repr.ok_or_else(|| meta.error("Missing `type_repr`"))?;
+        Ok(Self { type_repr, imports })
+    }
+}
+
+/// Generates a function returning `TypeInfo`, which can be placed where `PyStubType::type_input` is expected.
+impl ToTokens for OverrideType {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self { type_repr, imports } = self;
+        tokens.append_all(quote! {
+            || ::pyo3_stub_gen::TypeInfo {
+                name: #type_repr.to_string(),
+                import: [ #(::pyo3_stub_gen::ImportRef::Module(#imports.to_string())),* ].into(),
+            }
+        })
+    }
+}
+
+pub fn parse_gen_stub_attrs(attrs: &[Attribute]) -> Result<Vec<StubGenAttr>> {
+    let mut out = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("gen_stub") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("override_type") {
+                out.push(StubGenAttr::OverrideType(OverrideType::parse_nested(meta)?));
+            } else if meta.path.is_ident("override_return_type") {
+                out.push(StubGenAttr::OverrideReturnType(OverrideType::parse_nested(
+                    meta,
+                )?));
+            } else if meta.path.is_ident("richcmp") {
+                let mut ops = Vec::new();
+                meta.parse_nested_meta(|meta| {
+                    let op = meta
+                        .path
+                        .get_ident()
+                        .map(Ident::to_string)
+                        .filter(|op| RICHCMP_OPS.contains(&op.as_str()))
+                        .ok_or_else(|| {
+                            meta.error("Expected one of `eq`, `ne`, `lt`, `le`, `gt` or `ge`")
+                        })?;
+                    ops.push(op);
+                    Ok(())
+                })?;
+                out.push(StubGenAttr::Richcmp(ops));
+            } else if meta.path.is_ident("overload") {
+                out.push(StubGenAttr::Overload(Overload::parse_nested(meta)?));
+            } else if meta.path.is_ident("awaitable") {
+                let content;
+                parenthesized!(content in meta.input);
+                out.push(StubGenAttr::Awaitable(content.parse()?));
+            } else {
+                return Err(meta.error("Unknown `gen_stub` attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(out)
+}
+
+/// Comparison operators dispatched by `__richcmp__`, named as `__{op}__` methods
+pub const RICHCMP_OPS: [&str; 6] = ["eq", "ne", "lt", "le", "gt", "ge"];
+
+/// Find `#[gen_stub(richcmp(...))]`
+pub fn parse_richcmp_ops(attrs: &[Attribute]) -> Result<Option<Vec<String>>> {
+    Ok(parse_gen_stub_attrs(attrs)?
+        .into_iter()
+        .find_map(|attr| match attr {
+            StubGenAttr::Richcmp(ops) => Some(ops),
+            _ => None,
+        }))
+}
+
+/// Find `#[gen_stub(override_type(...))]`
+pub fn parse_override_type(attrs: &[Attribute]) -> Result<Option<OverrideType>> {
+    Ok(parse_gen_stub_attrs(attrs)?
+        .into_iter()
+        .find_map(|attr| match attr {
+            StubGenAttr::OverrideType(ty) => Some(ty),
+            _ => None,
+        }))
+}
+
+/// Find `#[gen_stub(override_return_type(...))]`
+pub fn parse_override_return_type(attrs: &[Attribute]) -> Result<Option<OverrideType>> {
+    Ok(parse_gen_stub_attrs(attrs)?
+        .into_iter()
+        .find_map(|attr| match attr {
+            StubGenAttr::OverrideReturnType(ty) => Some(ty),
+            _ => None,
+        }))
+}
+
+/// Find `#[gen_stub(awaitable(...))]`
+pub fn parse_awaitable(attrs: &[Attribute]) -> Result<Option<Type>> {
+    Ok(parse_gen_stub_attrs(attrs)?
+        .into_iter()
+        .find_map(|attr| match attr {
+            StubGenAttr::Awaitable(ty) => Some(ty),
+            _ => None,
+        }))
+}
+
+/// Collect `#[gen_stub(overload = "...")]`, and check that they define the function `name`
+/// taking `receiver`, i.e. `self` or `cls`, as the first parameter if it is a method.
+pub fn parse_overloads(
+    attrs: &[Attribute],
+    name: &str,
+    receiver: Option<&str>,
+) -> Result<Vec<Overload>> {
+    let mut overloads = Vec::new();
+    for attr in parse_gen_stub_attrs(attrs)? {
+        let StubGenAttr::Overload(overload) = attr else {
+            continue;
+        };
+        let value = overload.signature.value();
+        let def = value.trim_start();
+        let def = def.strip_prefix("async").unwrap_or(def).trim_start();
+        let (defined, params) = def
+            .strip_prefix("def")
+            .and_then(|def| def.split_once('('))
+            .map(|(defined, params)| (defined.trim(), params))
+            .unzip();
+        if defined != Some(name) {
+            return Err(Error::new(
+                overload.signature.span(),
+                format!("Expected a Python signature like `def {name}(...) -> ...`"),
+            ));
+        }
+        if let Some(receiver) = receiver {
+            let first = params
+                .and_then(|params| params.split([',', ')']).next())
+                .map(|first| first.split(':').next().unwrap_or(first).trim());
+            if first != Some(receiver) {
+                return Err(Error::new(
+                    overload.signature.span(),
+                    format!("Expected `{receiver}` as the first parameter like `def {name}({receiver}, ...) -> ...`"),
+                ));
+            }
+        }
+        overloads.push(overload);
+    }
+    Ok(overloads)
+}
+
+/// Remove `#[gen_stub(...)]` attributes since PyO3 and rustc do not know them
+pub fn prune_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path().is_ident("gen_stub"));
+}
+
+/// Remove `#[gen_stub(...)]` attributes from a function and its arguments
+pub fn prune_fn_attrs(attrs: &mut Vec<Attribute>, sig: &mut syn::Signature) {
+    prune_attrs(attrs);
+    for arg in sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(arg) = arg {
+            prune_attrs(&mut arg.attrs);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use syn::{parse_str, Fields, ItemStruct};
+    use syn::{parse_str, Fields, ItemFn, ItemStruct};
 
     #[test]
     fn test_parse_pyo3_attr() -> Result<()> {
@@ -176,4 +486,85 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_parse_extends() -> Result<()> {
+        let item: ItemStruct = parse_str(
+            r#"
+            #[pyclass(extends = pyo3::exceptions::PyException, subclass)]
+            pub struct MyError;
+            "#,
+        )?;
+        let attrs = parse_pyo3_attr(&item.attrs[0])?;
+        assert_eq!(
+            attrs,
+            vec![
+                Attr::Extends(syn::parse_quote!(pyo3::exceptions::PyException)),
+                Attr::Subclass
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_gen_stub_attr() -> Result<()> {
+        let item: ItemStruct = parse_str(
+            r#"
+            pub struct PyPlaceholder {
+                #[pyo3(get)]
+                #[gen_stub(override_type(type_repr = "typing.Callable[[int], str]", imports = ("typing",)))]
+                pub callback: PyObject,
+            }
+            "#,
+        )?;
+        if let Fields::Named(fields) = item.fields {
+            let attrs = parse_gen_stub_attrs(&fields.named[0].attrs)?;
+            assert_eq!(
+                attrs,
+                vec![StubGenAttr::OverrideType(OverrideType {
+                    type_repr: "typing.Callable[[int], str]".to_string(),
+                    imports: vec!["typing".to_string()],
+                })]
+            );
+        } else {
+            unreachable!()
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_overloads() -> Result<()> {
+        let item: ItemFn = parse_str(
+            r#"
+            #[gen_stub(overload = "def double(x: int) -> int")]
+            #[gen_stub(overload = "async def double(x: str) -> str:")]
+            #[gen_stub(overload(signature = "def double(x: collections.abc.Sequence[int]) -> list[int]", imports = ("collections.abc",)))]
+            fn double(x: &PyAny) -> PyResult<PyObject> { todo!() }
+            "#,
+        )?;
+        let overloads = parse_overloads(&item.attrs, "double", None)?;
+        assert_eq!(
+            overloads
+                .iter()
+                .map(|overload| overload.signature.value())
+                .collect::<Vec<_>>(),
+            vec![
+                "def double(x: int) -> int".to_string(),
+                "async def double(x: str) -> str:".to_string(),
+                "def double(x: collections.abc.Sequence[int]) -> list[int]".to_string(),
+            ]
+        );
+        assert_eq!(overloads[2].imports, vec!["collections.abc".to_string()]);
+        assert!(parse_overloads(&item.attrs, "triple", None).is_err());
+
+        let item: ItemFn = parse_str(
+            r#"
+            #[gen_stub(overload = "def scale(self, x: int) -> int")]
+            fn scale(&self, x: &PyAny) -> PyResult<PyObject> { todo!() }
+            "#,
+        )?;
+        assert!(parse_overloads(&item.attrs, "scale", Some("self")).is_ok());
+        assert!(parse_overloads(&item.attrs, "scale", Some("cls")).is_err());
+        Ok(())
+    }
 }
@@ -1,13 +1,20 @@
-use super::{escape_return_type, parse_pyo3_attrs, Attr};
+use super::{
+    arg::parse_args, escape_return_type, parse_override_return_type, parse_override_type,
+    parse_pyo3_attrs, replace_inner, Attr, OverrideType,
+};
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::{Error, Field, ImplItemFn, Result, Type};
+use syn::{Error, Field, ImplItemConst, ImplItemFn, Result, Type};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MemberInfo {
     name: String,
     r#type: Type,
+    /// Python type specified by `#[gen_stub(override_type(...))]` or `#[gen_stub(override_return_type(...))]`
+    override_type: Option<OverrideType>,
+    /// The value accepted by a setter rather than the value returned by a getter
+    is_setter: bool,
 }
 
 impl MemberInfo {
@@ -16,11 +23,111 @@ impl MemberInfo {
         Ok(attrs.iter().any(|attr| matches!(attr, Attr::Getter(_))))
     }
 
-    pub fn is_candidate_field(field: &Field) -> Result<bool> {
-        let Field { attrs, .. } = field;
-        Ok(parse_pyo3_attrs(attrs)?
-            .iter()
-            .any(|attr| matches!(attr, Attr::Get)))
+    pub fn is_setter_item(item: &ImplItemFn) -> Result<bool> {
+        let attrs = parse_pyo3_attrs(&item.attrs)?;
+        Ok(attrs.iter().any(|attr| matches!(attr, Attr::Setter(_))))
+    }
+
+    pub fn is_classattr_item(item: &ImplItemFn) -> Result<bool> {
+        let attrs = parse_pyo3_attrs(&item.attrs)?;
+        Ok(attrs.iter().any(|attr| matches!(attr, Attr::Classattr)))
+    }
+
+    pub fn is_classattr_const(item: &ImplItemConst) -> Result<bool> {
+        let attrs = parse_pyo3_attrs(&item.attrs)?;
+        Ok(attrs.iter().any(|attr| matches!(attr, Attr::Classattr)))
+    }
+
+    pub fn replace_self(&mut self, self_: &Type) {
+        replace_inner(&mut self.r#type, self_);
+    }
+
+    /// Parse a method decorated with `#[classattr]`, which returns the value of the class attribute
+    pub fn classattr(item: ImplItemFn) -> Result<Self> {
+        assert!(Self::is_classattr_item(&item)?);
+        let ImplItemFn { attrs, sig, .. } = item;
+        let name = parse_pyo3_attrs(&attrs)?
+            .into_iter()
+            .find_map(|attr| match attr {
+                Attr::Name(name) => Some(name),
+                _ => None,
+            })
+            .unwrap_or_else(|| sig.ident.to_string());
+        Ok(Self {
+            name,
+            r#type: escape_return_type(&sig.output).ok_or_else(|| {
+                Error::new(sig.ident.span(), "Class attribute must return a value")
+            })?,
+            override_type: parse_override_return_type(&attrs)?,
+            is_setter: false,
+        })
+    }
+
+    /// Parse an associated constant decorated with `#[classattr]`
+    pub fn classattr_const(item: ImplItemConst) -> Result<Self> {
+        assert!(Self::is_classattr_const(&item)?);
+        let ImplItemConst {
+            attrs, ident, ty, ..
+        } = item;
+        let name = parse_pyo3_attrs(&attrs)?
+            .into_iter()
+            .find_map(|attr| match attr {
+                Attr::Name(name) => Some(name),
+                _ => None,
+            })
+            .unwrap_or_else(|| ident.to_string());
+        Ok(Self {
+            name,
+            r#type: ty,
+            override_type: parse_override_type(&attrs)?,
+            is_setter: false,
+        })
+    }
+
+    /// Field of a tuple variant of enum, which is exposed as `_0`, `_1`, ...
+    pub fn tuple_field(index: usize, field: Field) -> Result<Self> {
+        Ok(Self {
+            name: format!("_{index}"),
+            override_type: parse_override_type(&field.attrs)?,
+            r#type: field.ty,
+            is_setter: false,
+        })
+    }
+
+    /// Setter of a `#[pyo3(set)]` field, which accepts the field type
+    pub fn as_setter(&self) -> Self {
+        Self {
+            is_setter: true,
+            ..self.clone()
+        }
+    }
+
+    /// Parse a method decorated with `#[setter]`
+    pub fn setter(item: ImplItemFn) -> Result<Self> {
+        assert!(Self::is_setter_item(&item)?);
+        let ImplItemFn { attrs, sig, .. } = item;
+        let name = parse_pyo3_attrs(&attrs)?
+            .into_iter()
+            .find_map(|attr| match attr {
+                Attr::Setter(name) => Some(name),
+                _ => None,
+            })
+            .flatten();
+        let name = name.unwrap_or_else(|| {
+            let ident = sig.ident.to_string();
+            ident.strip_prefix("set_").unwrap_or(&ident).to_string()
+        });
+        let span = sig.ident.span();
+        let value = parse_args(sig.inputs)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::new(span, "Setter must take a value"))?;
+        Ok(Self {
+            name,
+            r#type: value.r#type,
+            override_type: value.override_type,
+            is_setter: true,
+        })
     }
 }
 
@@ -32,9 +139,15 @@ impl TryFrom<ImplItemFn> for MemberInfo {
         let attrs = parse_pyo3_attrs(attrs)?;
         for attr in attrs {
             if let Attr::Getter(name) = attr {
+                let name = name.unwrap_or_else(|| {
+                    let ident = sig.ident.to_string();
+                    ident.strip_prefix("get_").unwrap_or(&ident).to_string()
+                });
                 return Ok(MemberInfo {
-                    name: name.unwrap_or(sig.ident.to_string()),
+                    name,
                     r#type: escape_return_type(&sig.output).expect("Getter must return a type"),
+                    override_type: parse_override_return_type(&item.attrs)?,
+                    is_setter: false,
                 });
             }
         }
@@ -57,18 +170,31 @@ impl TryFrom<Field> for MemberInfo {
         Ok(Self {
             name: field_name.unwrap_or(ident.unwrap().to_string()),
             r#type: ty,
+            override_type: parse_override_type(&attrs)?,
+            is_setter: false,
         })
     }
 }
 
 impl ToTokens for MemberInfo {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let Self { name, r#type: ty } = self;
-        let name = name.strip_prefix("get_").unwrap_or(name);
+        let Self {
+            name,
+            r#type: ty,
+            override_type,
+            is_setter,
+        } = self;
+        let type_tt = if let Some(override_type) = override_type {
+            quote! { #override_type }
+        } else if *is_setter {
+            quote! { <#ty as ::pyo3_stub_gen::PyStubType>::type_input }
+        } else {
+            quote! { <#ty as ::pyo3_stub_gen::PyStubType>::type_output }
+        };
         tokens.append_all(quote! {
             ::pyo3_stub_gen::type_info::MemberInfo {
                 name: #name,
-                r#type: <#ty as IntoPy<PyObject>>::type_output
+                r#type: #type_tt
             }
         })
     }
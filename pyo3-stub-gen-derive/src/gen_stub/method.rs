@@ -1,6 +1,8 @@
 use super::{
-    arg::parse_args, escape_return_type, extract_documents, parse_pyo3_attrs, quote_option,
-    ArgInfo, Attr, Signature,
+    arg::{mark_optional_args, mark_variadic_args, parse_args},
+    escape_return_type, extract_documents, is_self_type, parse_awaitable, parse_overloads,
+    parse_override_return_type, parse_pyo3_attrs, parse_richcmp_ops, quote_option,
+    unwrap_next_output, ArgInfo, Attr, Overload, OverrideType, Signature, RICHCMP_OPS,
 };
 
 use proc_macro2::TokenStream as TokenStream2;
@@ -15,12 +17,23 @@ pub struct MethodInfo {
     args: Vec<ArgInfo>,
     sig: Option<Signature>,
     r#return: Option<Type>,
+    override_return_type: Option<OverrideType>,
     doc: String,
     is_static: bool,
     is_class: bool,
+    is_async: bool,
+    /// Result type of the awaitable specified by `#[gen_stub(awaitable(T))]`
+    awaitable: Option<Type>,
+    /// Python signatures specified by `#[gen_stub(overload = "...")]`
+    overloads: Vec<Overload>,
+    /// Operators specified by `#[gen_stub(richcmp(...))]` for `__richcmp__`
+    richcmp_ops: Option<Vec<String>>,
+    /// Returns `Self`, `PyRef<Self>`, `PyRefMut<Self>` or `Py<Self>` before replacing `Self`
+    returns_self: bool,
 }
 
-fn replace_inner(ty: &mut Type, self_: &Type) {
+/// Replace `Self` in the type by the type of `impl` block
+pub fn replace_inner(ty: &mut Type, self_: &Type) {
     match ty {
         Type::Path(TypePath { path, .. }) => {
             if let Some(last) = path.segments.iter_mut().last() {
@@ -51,6 +64,50 @@ impl MethodInfo {
         if let Some(ret) = self.r#return.as_mut() {
             replace_inner(ret, self_);
         }
+        if let Some(sig) = self.sig.as_mut() {
+            sig.replace_self(self_);
+        }
+    }
+
+    /// Expand `__richcmp__(self, other, op)` into `__eq__(self, other)`, `__lt__(self, other)` and so on,
+    /// since type checkers do not know `__richcmp__`.
+    pub fn expand_richcmp(self) -> Vec<Self> {
+        if self.name != "__richcmp__" {
+            return vec![self];
+        }
+        let ops = self
+            .richcmp_ops
+            .clone()
+            .unwrap_or_else(|| RICHCMP_OPS.iter().map(|op| op.to_string()).collect());
+        ops.into_iter()
+            .map(|op| {
+                let mut args: Vec<ArgInfo> = self.args.iter().take(1).cloned().collect();
+                // `__eq__` and `__ne__` accept any object as in `object` class
+                if op == "eq" || op == "ne" {
+                    for arg in &mut args {
+                        arg.override_type = Some(OverrideType {
+                            type_repr: "object".to_string(),
+                            imports: Vec::new(),
+                        });
+                    }
+                }
+                MethodInfo {
+                    name: format!("__{op}__"),
+                    args,
+                    sig: None,
+                    r#return: self.r#return.clone(),
+                    override_return_type: self.override_return_type.clone(),
+                    doc: self.doc.clone(),
+                    is_static: false,
+                    is_class: false,
+                    is_async: self.is_async,
+                    awaitable: self.awaitable.clone(),
+                    overloads: Vec::new(),
+                    richcmp_ops: None,
+                    returns_self: self.returns_self,
+                }
+            })
+            .collect()
     }
 }
 
@@ -59,12 +116,15 @@ impl TryFrom<ImplItemFn> for MethodInfo {
     fn try_from(item: ImplItemFn) -> Result<Self> {
         let ImplItemFn { attrs, sig, .. } = item;
         let doc = extract_documents(&attrs).join("\n");
-        let attrs = parse_pyo3_attrs(&attrs)?;
+        let override_return_type = parse_override_return_type(&attrs)?;
+        let richcmp_ops = parse_richcmp_ops(&attrs)?;
+        let awaitable = parse_awaitable(&attrs)?;
+        let pyo3_attrs = parse_pyo3_attrs(&attrs)?;
         let mut method_name = None;
         let mut text_sig = Signature::overriding_operator(&sig);
         let mut is_static = false;
         let mut is_class = false;
-        for attr in attrs {
+        for attr in pyo3_attrs {
             match attr {
                 Attr::Name(name) => method_name = Some(name),
                 Attr::Signature(text_sig_) => text_sig = Some(text_sig_),
@@ -74,15 +134,39 @@ impl TryFrom<ImplItemFn> for MethodInfo {
             }
         }
         let name = method_name.unwrap_or(sig.ident.to_string());
-        let r#return = escape_return_type(&sig.output);
+        let receiver = if is_static {
+            None
+        } else if is_class {
+            Some("cls")
+        } else {
+            Some("self")
+        };
+        let overloads = parse_overloads(&attrs, &name, receiver)?;
+        let mut r#return = escape_return_type(&sig.output);
+        if name == "__next__" || name == "__anext__" {
+            r#return = r#return.map(|ty| unwrap_next_output(&ty).clone());
+        }
+        let returns_self = r#return.as_ref().is_some_and(is_self_type);
+        let mut args = parse_args(sig.inputs)?;
+        if let Some(text_sig) = &text_sig {
+            mark_variadic_args(&mut args, text_sig);
+        } else {
+            mark_optional_args(&mut args);
+        }
         Ok(MethodInfo {
             name,
             sig: text_sig,
-            args: parse_args(sig.inputs)?,
+            args,
             r#return,
+            override_return_type,
             doc,
             is_static,
             is_class,
+            is_async: sig.asyncness.is_some(),
+            awaitable,
+            overloads,
+            richcmp_ops,
+            returns_self,
         })
     }
 }
@@ -92,15 +176,29 @@ impl ToTokens for MethodInfo {
         let Self {
             name,
             r#return: ret,
+            override_return_type,
             args,
             sig,
             doc,
             is_class,
             is_static,
+            is_async,
+            awaitable,
+            overloads,
+            richcmp_ops: _,
+            returns_self,
         } = self;
         let sig_tt = quote_option(sig);
-        let ret_tt = if let Some(ret) = ret {
-            quote! { <#ret as IntoPy<::pyo3::PyObject>>::type_output }
+        let is_return_overridden = override_return_type.is_some() || awaitable.is_some();
+        let ret_tt = if let Some(override_type) = override_return_type {
+            quote! { #override_type }
+        } else if let Some(awaitable) = awaitable {
+            quote! { ::pyo3_stub_gen::type_info::awaitable_output::<#awaitable> }
+        } else if *returns_self && (name == "__iter__" || name == "__aiter__") {
+            // Iterators returning themselves are annotated as `Self` to be kept in subclasses
+            quote! { || ::pyo3_stub_gen::TypeInfo::imported("typing_extensions", "Self") }
+        } else if let Some(ret) = ret {
+            quote! { <#ret as ::pyo3_stub_gen::PyStubType>::type_output }
         } else {
             quote! { ::pyo3_stub_gen::type_info::no_return_type_output }
         };
@@ -113,7 +211,39 @@ impl ToTokens for MethodInfo {
                 doc: #doc,
                 is_static: #is_static,
                 is_class: #is_class,
+                is_async: #is_async,
+                overloads: &[ #(#overloads),* ],
+                is_return_overridden: #is_return_overridden,
             }
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_expand_richcmp() -> Result<()> {
+        let item: ImplItemFn = parse_str(
+            r#"
+            #[gen_stub(richcmp(eq, lt))]
+            fn __richcmp__(&self, other: PyRef<'_, Self>, op: CompareOp) -> bool {
+                todo!()
+            }
+            "#,
+        )?;
+        let mut method = MethodInfo::try_from(item)?;
+        method.replace_self(&parse_str("Rectangle")?);
+        let methods = method.expand_richcmp();
+        let names: Vec<_> = methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["__eq__", "__lt__"]);
+        assert_eq!(
+            methods[0].args[0].override_type.as_ref().unwrap().type_repr,
+            "object"
+        );
+        assert!(methods[1].args[0].override_type.is_none());
+        Ok(())
+    }
+}
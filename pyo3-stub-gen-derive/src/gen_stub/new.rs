@@ -1,8 +1,11 @@
-use super::{parse_args, parse_pyo3_attrs, quote_option, ArgInfo, Attr, Signature};
+use super::{
+    mark_optional_args, mark_variadic_args, parse_args, parse_pyo3_attrs, quote_option, ArgInfo,
+    Attr, Signature,
+};
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::{Error, ImplItemFn, Result};
+use syn::{Error, ImplItemFn, Result, Type};
 
 #[derive(Debug)]
 pub struct NewInfo {
@@ -11,6 +14,12 @@ pub struct NewInfo {
 }
 
 impl NewInfo {
+    pub fn replace_self(&mut self, self_: &Type) {
+        if let Some(sig) = self.sig.as_mut() {
+            sig.replace_self(self_);
+        }
+    }
+
     pub fn is_candidate(item: &ImplItemFn) -> Result<bool> {
         let attrs = parse_pyo3_attrs(&item.attrs)?;
         Ok(attrs.iter().any(|attr| matches!(attr, Attr::New)))
@@ -29,10 +38,13 @@ impl TryFrom<ImplItemFn> for NewInfo {
                 new_sig = Some(text_sig);
             }
         }
-        Ok(NewInfo {
-            args: parse_args(sig.inputs)?,
-            sig: new_sig,
-        })
+        let mut args = parse_args(sig.inputs)?;
+        if let Some(new_sig) = &new_sig {
+            mark_variadic_args(&mut args, new_sig);
+        } else {
+            mark_optional_args(&mut args);
+        }
+        Ok(NewInfo { args, sig: new_sig })
     }
 }
 
@@ -2,16 +2,40 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{parse_quote, Error, ItemStruct, Result, Type};
 
-use super::{extract_documents, parse_pyo3_attrs, util::quote_option, Attr, MemberInfo};
+use super::{extract_documents, parse_pyo3_attrs, util::quote_option, Attr, MemberInfo, StubType};
 
 pub struct PyClassInfo {
     pyclass_name: String,
     struct_type: Type,
     module: Option<String>,
     members: Vec<MemberInfo>,
+    setters: Vec<MemberInfo>,
+    base: Option<Type>,
+    subclass: bool,
+    eq: bool,
+    ord: bool,
+    hash: bool,
+    str: bool,
+    frozen: bool,
     doc: String,
 }
 
+impl From<&PyClassInfo> for StubType {
+    fn from(info: &PyClassInfo) -> Self {
+        let PyClassInfo {
+            pyclass_name,
+            module,
+            struct_type,
+            ..
+        } = info;
+        Self {
+            ty: struct_type.clone(),
+            name: pyclass_name.clone(),
+            module: module.clone(),
+        }
+    }
+}
+
 impl TryFrom<ItemStruct> for PyClassInfo {
     type Error = Error;
     fn try_from(item: ItemStruct) -> Result<Self> {
@@ -25,6 +49,10 @@ impl TryFrom<ItemStruct> for PyClassInfo {
         let mut pyclass_name = None;
         let mut module = None;
         let mut is_get_all = false;
+        let mut is_set_all = false;
+        let mut base = None;
+        let mut subclass = false;
+        let (mut eq, mut ord, mut hash, mut str, mut frozen) = (false, false, false, false, false);
         for attr in parse_pyo3_attrs(&attrs)? {
             match attr {
                 Attr::Name(name) => pyclass_name = Some(name),
@@ -32,14 +60,33 @@ impl TryFrom<ItemStruct> for PyClassInfo {
                     module = Some(name);
                 }
                 Attr::GetAll => is_get_all = true,
+                Attr::SetAll => is_set_all = true,
+                Attr::Extends(ty) => base = Some(ty),
+                Attr::Subclass => subclass = true,
+                Attr::Eq => eq = true,
+                Attr::Ord => ord = true,
+                Attr::Hash => hash = true,
+                Attr::Str => str = true,
+                Attr::Frozen => frozen = true,
                 _ => {}
             }
         }
         let pyclass_name = pyclass_name.unwrap_or_else(|| ident.to_string());
         let mut members = Vec::new();
+        let mut setters = Vec::new();
         for field in fields {
-            if is_get_all || MemberInfo::is_candidate_field(&field)? {
-                members.push(MemberInfo::try_from(field)?)
+            let field_attrs = parse_pyo3_attrs(&field.attrs)?;
+            let is_get = is_get_all || field_attrs.contains(&Attr::Get);
+            let is_set = is_set_all || field_attrs.contains(&Attr::Set);
+            if !is_get && !is_set {
+                continue;
+            }
+            let member = MemberInfo::try_from(field)?;
+            if is_set {
+                setters.push(member.as_setter());
+            }
+            if is_get {
+                members.push(member);
             }
         }
         let doc = extract_documents(&attrs).join("\n");
@@ -47,6 +94,14 @@ impl TryFrom<ItemStruct> for PyClassInfo {
             struct_type,
             pyclass_name,
             members,
+            setters,
+            base,
+            subclass,
+            eq,
+            ord,
+            hash,
+            str,
+            frozen,
             module,
             doc,
         })
@@ -59,15 +114,36 @@ impl ToTokens for PyClassInfo {
             pyclass_name,
             struct_type,
             members,
+            setters,
+            base,
+            subclass,
+            eq,
+            ord,
+            hash,
+            str,
+            frozen,
             doc,
             module,
         } = self;
         let module = quote_option(module);
+        let base = quote_option(
+            &base
+                .as_ref()
+                .map(|base| quote! { <#base as ::pyo3_stub_gen::PyStubType>::type_output }),
+        );
         tokens.append_all(quote! {
             ::pyo3_stub_gen::type_info::PyClassInfo {
                 pyclass_name: #pyclass_name,
                 struct_id: std::any::TypeId::of::<#struct_type>,
                 members: &[ #( #members),* ],
+                setters: &[ #( #setters),* ],
+                base: #base,
+                subclass: #subclass,
+                eq: #eq,
+                ord: #ord,
+                hash: #hash,
+                str: #str,
+                frozen: #frozen,
                 module: #module,
                 doc: #doc,
             }
@@ -78,6 +154,7 @@ impl ToTokens for PyClassInfo {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen_stub::util::format_as_value;
     use syn::parse_str;
 
     #[test]
@@ -89,7 +166,7 @@ mod test {
                 Debug, Clone, PyNeg, PyAdd, PySub, PyMul, PyDiv, PyMod, PyPow, PyCmp, PyIndex, PyPrint,
             )]
             pub struct PyPlaceholder {
-                #[pyo3(get)]
+                #[pyo3(get, set)]
                 pub name: String,
                 #[pyo3(get)]
                 pub ndim: usize,
@@ -107,33 +184,34 @@ mod test {
             members: &[
                 ::pyo3_stub_gen::type_info::MemberInfo {
                     name: "name",
-                    r#type: <String as IntoPy<PyObject>>::type_output,
+                    r#type: <String as ::pyo3_stub_gen::PyStubType>::type_output,
                 },
                 ::pyo3_stub_gen::type_info::MemberInfo {
                     name: "ndim",
-                    r#type: <usize as IntoPy<PyObject>>::type_output,
+                    r#type: <usize as ::pyo3_stub_gen::PyStubType>::type_output,
                 },
                 ::pyo3_stub_gen::type_info::MemberInfo {
                     name: "description",
-                    r#type: <Option<String> as IntoPy<PyObject>>::type_output,
+                    r#type: <Option<String> as ::pyo3_stub_gen::PyStubType>::type_output,
+                },
+            ],
+            setters: &[
+                ::pyo3_stub_gen::type_info::MemberInfo {
+                    name: "name",
+                    r#type: <String as ::pyo3_stub_gen::PyStubType>::type_input,
                 },
             ],
+            base: None,
+            subclass: false,
+            eq: false,
+            ord: false,
+            hash: false,
+            str: false,
+            frozen: false,
             module: Some("my_module"),
             doc: "",
         }
         "###);
         Ok(())
     }
-
-    fn format_as_value(tt: TokenStream2) -> String {
-        let ttt = quote! { const _: () = #tt; };
-        let formatted = prettyplease::unparse(&syn::parse_file(&ttt.to_string()).unwrap());
-        formatted
-            .trim()
-            .strip_prefix("const _: () = ")
-            .unwrap()
-            .strip_suffix(';')
-            .unwrap()
-            .to_string()
-    }
 }
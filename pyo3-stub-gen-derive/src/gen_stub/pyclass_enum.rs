@@ -1,17 +1,143 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::{parse_quote, Error, ItemEnum, Result, Type};
+use syn::{parse_quote, Error, Fields, Ident, ItemEnum, Result, Type, Variant};
 
-use super::{extract_documents, parse_pyo3_attrs, util::quote_option, Attr};
+use super::{extract_documents, parse_pyo3_attrs, util::quote_option, Attr, MemberInfo, StubType};
 
 pub struct PyEnumInfo {
     pyclass_name: String,
     enum_type: Type,
     module: Option<String>,
-    variants: Vec<String>,
+    variants: Vec<VariantInfo>,
     doc: String,
 }
 
+struct VariantInfo {
+    /// Name of the variant in Rust
+    ident: Ident,
+    /// Name of the variant in Python
+    name: String,
+    kind: TokenStream2,
+    fields: Vec<MemberInfo>,
+    /// Arguments of `__new__`, which accept the input types of fields as setters do
+    args: Vec<MemberInfo>,
+}
+
+impl TryFrom<Variant> for VariantInfo {
+    type Error = Error;
+    fn try_from(variant: Variant) -> Result<Self> {
+        let mut name = None;
+        for attr in parse_pyo3_attrs(&variant.attrs)? {
+            if let Attr::Name(var_name) = attr {
+                name = Some(var_name);
+            }
+        }
+        let (kind, fields) = match variant.fields {
+            Fields::Unit => (quote! { Unit }, Vec::new()),
+            Fields::Unnamed(fields) => (
+                quote! { Tuple },
+                fields
+                    .unnamed
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, field)| MemberInfo::tuple_field(index, field))
+                    .collect::<Result<_>>()?,
+            ),
+            Fields::Named(fields) => (
+                quote! { Struct },
+                fields
+                    .named
+                    .into_iter()
+                    .map(MemberInfo::try_from)
+                    .collect::<Result<_>>()?,
+            ),
+        };
+        let args = fields.iter().map(MemberInfo::as_setter).collect();
+        Ok(Self {
+            name: name.unwrap_or_else(|| variant.ident.to_string()),
+            ident: variant.ident,
+            kind,
+            fields,
+            args,
+        })
+    }
+}
+
+impl ToTokens for VariantInfo {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            name,
+            kind,
+            fields,
+            args,
+            ..
+        } = self;
+        tokens.append_all(quote! {
+            ::pyo3_stub_gen::type_info::VariantInfo {
+                name: #name,
+                kind: ::pyo3_stub_gen::type_info::VariantKind::#kind,
+                fields: &[ #(#fields),* ],
+                args: &[ #(#args),* ],
+            }
+        })
+    }
+}
+
+impl From<&PyEnumInfo> for StubType {
+    fn from(info: &PyEnumInfo) -> Self {
+        let PyEnumInfo {
+            pyclass_name,
+            module,
+            enum_type,
+            ..
+        } = info;
+        Self {
+            ty: enum_type.clone(),
+            name: pyclass_name.clone(),
+            module: module.clone(),
+        }
+    }
+}
+
+/// Implementation of `PyClassEnum` trait mapping Rust variant names to Python names
+pub struct PyClassEnumImpl {
+    enum_type: Type,
+    variants: Vec<(String, String)>,
+}
+
+impl From<&PyEnumInfo> for PyClassEnumImpl {
+    fn from(info: &PyEnumInfo) -> Self {
+        Self {
+            enum_type: info.enum_type.clone(),
+            variants: info
+                .variants
+                .iter()
+                .map(|variant| (variant.ident.to_string(), variant.name.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl ToTokens for PyClassEnumImpl {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            enum_type,
+            variants,
+        } = self;
+        let (idents, names): (Vec<_>, Vec<_>) = variants.iter().cloned().unzip();
+        tokens.append_all(quote! {
+            impl ::pyo3_stub_gen::type_info::PyClassEnum for #enum_type {
+                fn variant_name(variant: &str) -> Option<&'static str> {
+                    match variant {
+                        #(#idents => Some(#names),)*
+                        _ => None,
+                    }
+                }
+            }
+        })
+    }
+}
+
 impl TryFrom<ItemEnum> for PyEnumInfo {
     type Error = Error;
     fn try_from(
@@ -36,16 +162,8 @@ impl TryFrom<ItemEnum> for PyEnumInfo {
         let pyclass_name = pyclass_name.unwrap_or_else(|| ident.to_string());
         let variants = variants
             .into_iter()
-            .map(|var| -> Result<String> {
-                let mut var_name = None;
-                for attr in parse_pyo3_attrs(&var.attrs)? {
-                    if let Attr::Name(name) = attr {
-                        var_name = Some(name);
-                    }
-                }
-                Ok(var_name.unwrap_or_else(|| var.ident.to_string()))
-            })
-            .collect::<Result<Vec<String>>>()?;
+            .map(VariantInfo::try_from)
+            .collect::<Result<Vec<_>>>()?;
         Ok(Self {
             doc,
             enum_type: struct_type,
@@ -77,3 +195,103 @@ impl ToTokens for PyEnumInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_stub::util::format_as_value;
+    use syn::parse_str;
+
+    #[test]
+    fn test_variant_names() -> Result<()> {
+        let input: ItemEnum = parse_str(
+            r#"
+            #[pyclass(name = "DataType")]
+            pub enum PyDataType {
+                #[pyo3(name = "FLOAT")]
+                Float,
+                Integer,
+            }
+            "#,
+        )?;
+        let out = PyClassEnumImpl::from(&PyEnumInfo::try_from(input)?).to_token_stream();
+        insta::assert_snapshot!(prettyplease::unparse(&syn::parse2(out)?), @r###"
+        impl ::pyo3_stub_gen::type_info::PyClassEnum for PyDataType {
+            fn variant_name(variant: &str) -> Option<&'static str> {
+                match variant {
+                    "Float" => Some("FLOAT"),
+                    "Integer" => Some("Integer"),
+                    _ => None,
+                }
+            }
+        }
+        "###);
+        Ok(())
+    }
+
+    #[test]
+    fn test_complex_enum() -> Result<()> {
+        let input: ItemEnum = parse_str(
+            r#"
+            #[pyclass]
+            pub enum Shape {
+                Circle { radius: f64 },
+                #[pyo3(name = "Rect")]
+                Rectangle(f64, f64),
+            }
+            "#,
+        )?;
+        let out = PyEnumInfo::try_from(input)?.to_token_stream();
+        insta::assert_snapshot!(format_as_value(out), @r###"
+        ::pyo3_stub_gen::type_info::PyEnumInfo {
+            pyclass_name: "Shape",
+            enum_id: std::any::TypeId::of::<Shape>,
+            variants: &[
+                ::pyo3_stub_gen::type_info::VariantInfo {
+                    name: "Circle",
+                    kind: ::pyo3_stub_gen::type_info::VariantKind::Struct,
+                    fields: &[
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "radius",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_output,
+                        },
+                    ],
+                    args: &[
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "radius",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_input,
+                        },
+                    ],
+                },
+                ::pyo3_stub_gen::type_info::VariantInfo {
+                    name: "Rect",
+                    kind: ::pyo3_stub_gen::type_info::VariantKind::Tuple,
+                    fields: &[
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "_0",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_output,
+                        },
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "_1",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_output,
+                        },
+                    ],
+                    args: &[
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "_0",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_input,
+                        },
+                        ::pyo3_stub_gen::type_info::MemberInfo {
+                            name: "_1",
+                            r#type: <f64 as ::pyo3_stub_gen::PyStubType>::type_input,
+                        },
+                    ],
+                },
+            ],
+            module: None,
+            doc: "",
+        }
+        "###);
+        Ok(())
+    }
+}
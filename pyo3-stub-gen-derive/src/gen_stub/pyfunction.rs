@@ -6,17 +6,24 @@ use syn::{
 };
 
 use super::{
-    escape_return_type, extract_documents, parse_args, parse_pyo3_attrs, quote_option, ArgInfo,
-    Attr, Signature,
+    escape_return_type, extract_documents, mark_optional_args, mark_variadic_args, parse_args,
+    parse_awaitable, parse_overloads, parse_override_return_type, parse_pyo3_attrs, quote_option,
+    ArgInfo, Attr, Overload, OverrideType, Signature,
 };
 
 pub struct PyFunctionInfo {
     name: String,
     args: Vec<ArgInfo>,
     r#return: Option<Type>,
+    override_return_type: Option<OverrideType>,
     sig: Option<Signature>,
     doc: String,
     module: Option<String>,
+    is_async: bool,
+    /// Result type of the awaitable specified by `#[gen_stub(awaitable(T))]`
+    awaitable: Option<Type>,
+    /// Python signatures specified by `#[gen_stub(overload = "...")]`
+    overloads: Vec<Overload>,
 }
 
 struct ModuleAttr {
@@ -50,8 +57,10 @@ impl TryFrom<ItemFn> for PyFunctionInfo {
     type Error = Error;
     fn try_from(item: ItemFn) -> Result<Self> {
         let doc = extract_documents(&item.attrs).join("\n");
-        let args = parse_args(item.sig.inputs)?;
+        let mut args = parse_args(item.sig.inputs)?;
         let r#return = escape_return_type(&item.sig.output);
+        let override_return_type = parse_override_return_type(&item.attrs)?;
+        let awaitable = parse_awaitable(&item.attrs)?;
         let mut name = None;
         let mut sig = None;
         for attr in parse_pyo3_attrs(&item.attrs)? {
@@ -62,13 +71,23 @@ impl TryFrom<ItemFn> for PyFunctionInfo {
             }
         }
         let name = name.unwrap_or_else(|| item.sig.ident.to_string());
+        let overloads = parse_overloads(&item.attrs, &name, None)?;
+        if let Some(sig) = &sig {
+            mark_variadic_args(&mut args, sig);
+        } else {
+            mark_optional_args(&mut args);
+        }
         Ok(Self {
             args,
             sig,
             r#return,
+            override_return_type,
             name,
             doc,
             module: None,
+            is_async: item.sig.asyncness.is_some(),
+            awaitable,
+            overloads,
         })
     }
 }
@@ -78,13 +97,21 @@ impl ToTokens for PyFunctionInfo {
         let Self {
             args,
             r#return: ret,
+            override_return_type,
             name,
             doc,
             sig,
             module,
+            is_async,
+            awaitable,
+            overloads,
         } = self;
-        let ret_tt = if let Some(ret) = ret {
-            quote! { <#ret as IntoPy<::pyo3::PyObject>>::type_output }
+        let ret_tt = if let Some(override_type) = override_return_type {
+            quote! { #override_type }
+        } else if let Some(awaitable) = awaitable {
+            quote! { ::pyo3_stub_gen::type_info::awaitable_output::<#awaitable> }
+        } else if let Some(ret) = ret {
+            quote! { <#ret as ::pyo3_stub_gen::PyStubType>::type_output }
         } else {
             quote! { ::pyo3_stub_gen::type_info::no_return_type_output }
         };
@@ -98,6 +125,8 @@ impl ToTokens for PyFunctionInfo {
                 doc: #doc,
                 signature: #sig_tt,
                 module: #module_tt,
+                is_async: #is_async,
+                overloads: &[ #(#overloads),* ],
             }
         })
     }
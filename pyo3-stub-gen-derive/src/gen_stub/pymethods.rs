@@ -8,6 +8,8 @@ pub struct PyMethodsInfo {
     struct_id: Type,
     new: Option<NewInfo>,
     getters: Vec<MemberInfo>,
+    setters: Vec<MemberInfo>,
+    class_attrs: Vec<MemberInfo>,
     methods: Vec<MethodInfo>,
 }
 
@@ -17,18 +19,34 @@ impl TryFrom<ItemImpl> for PyMethodsInfo {
         let struct_id = *item.self_ty.clone();
         let mut new = None;
         let mut getters = Vec::new();
+        let mut setters = Vec::new();
+        let mut class_attrs = Vec::new();
         let mut methods = Vec::new();
 
         for inner in item.items {
-            if let ImplItem::Fn(item_fn) = inner {
+            if let ImplItem::Const(item_const) = inner {
+                if MemberInfo::is_classattr_const(&item_const)? {
+                    let mut class_attr = MemberInfo::classattr_const(item_const)?;
+                    class_attr.replace_self(&item.self_ty);
+                    class_attrs.push(class_attr);
+                }
+            } else if let ImplItem::Fn(item_fn) = inner {
                 if NewInfo::is_candidate(&item_fn)? {
-                    new = Some(NewInfo::try_from(item_fn)?);
+                    let mut new_info = NewInfo::try_from(item_fn)?;
+                    new_info.replace_self(&item.self_ty);
+                    new = Some(new_info);
                 } else if MemberInfo::is_candidate_item(&item_fn)? {
                     getters.push(MemberInfo::try_from(item_fn)?);
+                } else if MemberInfo::is_setter_item(&item_fn)? {
+                    setters.push(MemberInfo::setter(item_fn)?);
+                } else if MemberInfo::is_classattr_item(&item_fn)? {
+                    let mut class_attr = MemberInfo::classattr(item_fn)?;
+                    class_attr.replace_self(&item.self_ty);
+                    class_attrs.push(class_attr);
                 } else {
                     let mut method = MethodInfo::try_from(item_fn)?;
                     method.replace_self(&item.self_ty);
-                    methods.push(method);
+                    methods.extend(method.expand_richcmp());
                 }
             }
         }
@@ -36,6 +54,8 @@ impl TryFrom<ItemImpl> for PyMethodsInfo {
             struct_id,
             new,
             getters,
+            setters,
+            class_attrs,
             methods,
         })
     }
@@ -47,15 +67,22 @@ impl ToTokens for PyMethodsInfo {
             struct_id,
             new,
             getters,
+            setters,
+            class_attrs,
             methods,
         } = self;
         let new_tt = quote_option(new);
         tokens.append_all(quote! {
             ::pyo3_stub_gen::type_info::PyMethodsInfo {
                 struct_id: std::any::TypeId::of::<#struct_id>,
+                struct_name: std::any::type_name::<#struct_id>,
                 new: #new_tt,
                 getters: &[ #(#getters),* ],
+                setters: &[ #(#setters),* ],
+                class_attrs: &[ #(#class_attrs),* ],
                 methods: &[ #(#methods),* ],
+                file: ::std::file!(),
+                line: ::std::line!(),
             }
         })
     }
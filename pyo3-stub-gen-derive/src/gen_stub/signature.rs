@@ -1,10 +1,12 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
+    ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    token, Expr, Ident, Result, Token,
+    token, Expr, ExprLit, ExprPath, ExprUnary, Ident, Lit, Path, PathArguments, Result, Token,
+    Type, TypePath, UnOp,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -12,6 +14,7 @@ enum SignatureArg {
     Ident(Ident),
     Assign(Ident, Token![=], Expr),
     Star(Token![*]),
+    Slash(Token![/]),
     Args(Token![*], Ident),
     Keywords(Token![*], Token![*], Ident),
 }
@@ -27,6 +30,8 @@ impl Parse for SignatureArg {
             } else {
                 Ok(SignatureArg::Star(star))
             }
+        } else if input.peek(Token![/]) {
+            Ok(SignatureArg::Slash(input.parse()?))
         } else if input.peek(Ident) {
             let ident = Ident::parse(input)?;
             if input.peek(Token![=]) {
@@ -35,26 +40,182 @@ impl Parse for SignatureArg {
                 Ok(SignatureArg::Ident(ident))
             }
         } else {
-            dbg!(input);
-            todo!()
+            Err(input.error("Unexpected token in signature"))
         }
     }
 }
 
 impl ToTokens for SignatureArg {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let arg = quote! { ::pyo3_stub_gen::type_info::SignatureArg };
         match self {
-            SignatureArg::Ident(ident) => tokens.append_all(quote! { #ident }),
-            SignatureArg::Assign(ident, _eq, _value) => tokens.append_all(quote! { #ident = ... }),
-            SignatureArg::Star(star) => tokens.append_all(quote! { #star }),
-            SignatureArg::Args(star, ident) => tokens.append_all(quote! { #star #ident }),
-            SignatureArg::Keywords(star1, star2, ident) => {
-                tokens.append_all(quote! { #star1 #star2 #ident })
+            SignatureArg::Ident(ident) => {
+                let name = ident.unraw().to_string();
+                tokens.append_all(quote! { #arg::Ident(#name) })
+            }
+            SignatureArg::Assign(ident, _eq, value) => {
+                let name = ident.unraw().to_string();
+                let default = default_value(value).unwrap_or_else(|| {
+                    quote! { || ::pyo3_stub_gen::TypeInfo::builtin("...") }
+                });
+                tokens.append_all(quote! { #arg::Assign(#name, #default) })
+            }
+            SignatureArg::Star(_) => tokens.append_all(quote! { #arg::Star }),
+            SignatureArg::Slash(_) => tokens.append_all(quote! { #arg::Slash }),
+            SignatureArg::Args(_, ident) => {
+                let name = ident.unraw().to_string();
+                tokens.append_all(quote! { #arg::Args(#name) })
+            }
+            SignatureArg::Keywords(_, _, ident) => {
+                let name = ident.unraw().to_string();
+                tokens.append_all(quote! { #arg::Keywords(#name) })
             }
         }
     }
 }
 
+/// Python expression of a literal default value, e.g. `30`, `"fast"` or `None`
+fn python_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Int(int) => Some(int.base10_digits().to_string()),
+            Lit::Float(float) => Some(float.base10_digits().to_string()),
+            Lit::Bool(bool) => Some(if bool.value { "True" } else { "False" }.to_string()),
+            Lit::Str(str) => python_str(&str.value()),
+            Lit::Char(char) => python_str(&char.value().to_string()),
+            _ => None,
+        },
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(_) | Lit::Float(_),
+                ..
+            }) => Some(format!("-{}", python_literal(expr)?)),
+            _ => None,
+        },
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("None") => Some("None".to_string()),
+        _ => None,
+    }
+}
+
+fn python_str(value: &str) -> Option<String> {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => return None,
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Some(out)
+}
+
+/// Replace `Self` at the head of paths in the default value, e.g. `Self::North`,
+/// since the generated code is placed outside of the `impl` block.
+fn replace_self(expr: &mut Expr, self_: &Type) {
+    match expr {
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) => {
+            let Type::Path(TypePath {
+                qself: None,
+                path: self_path,
+            }) = self_
+            else {
+                return;
+            };
+            if path
+                .segments
+                .first()
+                .is_none_or(|head| head.ident != "Self")
+            {
+                return;
+            }
+            let mut replaced: Path = self_path.clone();
+            for segment in replaced.segments.iter_mut() {
+                // Generic arguments in expression require turbofish, e.g. `Foo::<T>::A`
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    args.colon2_token = Some(Default::default());
+                }
+            }
+            replaced
+                .segments
+                .extend(path.segments.iter().skip(1).cloned());
+            *path = replaced;
+        }
+        Expr::Paren(paren) => replace_self(&mut paren.expr, self_),
+        Expr::Call(call) => {
+            for arg in call.args.iter_mut() {
+                replace_self(arg, self_);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Closure `fn() -> TypeInfo` rendering the default value as a Python expression.
+///
+/// Besides literals, `Some(value)`, integer constants like `i64::MAX`,
+/// and variants of `#[gen_stub_pyclass_enum]` enums like `Number::Float` are supported.
+/// `None` is returned if the value cannot be expressed in Python.
+fn default_value(expr: &Expr) -> Option<TokenStream2> {
+    if let Some(literal) = python_literal(expr) {
+        return Some(quote! { || ::pyo3_stub_gen::TypeInfo::builtin(#literal) });
+    }
+    match expr {
+        Expr::Paren(paren) => default_value(&paren.expr),
+        Expr::Call(call) if call.args.len() == 1 => match call.func.as_ref() {
+            Expr::Path(ExprPath { path, .. }) if path.is_ident("Some") => {
+                default_value(&call.args[0])
+            }
+            _ => None,
+        },
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) if path.segments.len() >= 2 => {
+            // `Self` cannot be used outside of the `impl` block, see [replace_self]
+            if path.segments[0].ident == "Self" {
+                return None;
+            }
+            let mut ty = path.clone();
+            let last = ty.segments.pop()?.into_value().ident;
+            ty.segments.pop_punct();
+            let last_name = last.to_string();
+            const INTS: &[&str] = &[
+                "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+                "isize",
+            ];
+            if INTS.iter().any(|int| ty.is_ident(int)) && (last == "MIN" || last == "MAX") {
+                return Some(quote! {
+                    || ::pyo3_stub_gen::TypeInfo::builtin(&#path.to_string())
+                });
+            }
+            // Enum variants are written in UpperCamelCase, unlike associated constants
+            let is_variant = last_name.starts_with(|c: char| c.is_ascii_uppercase())
+                && last_name.chars().any(|c| c.is_ascii_lowercase());
+            if !is_variant {
+                return None;
+            }
+            // Rendered as `Enum.Variant` only if the type is `#[gen_stub_pyclass_enum]`,
+            // and `...` otherwise, e.g. for associated constants in UpperCamelCase
+            Some(quote! {
+                || {
+                    use ::pyo3_stub_gen::type_info::{EnumVariantDefault as _, OtherDefault as _};
+                    (&::pyo3_stub_gen::type_info::DefaultValue::<#ty>::new()).variant(#last_name)
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Signature {
     paren: token::Paren,
@@ -72,17 +233,34 @@ impl Parse for Signature {
 
 impl ToTokens for Signature {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let sig = self
-            .args
-            .iter()
-            .map(|arg| arg.to_token_stream().to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        tokens.append_all(quote! { #sig });
+        let args = self.args.iter();
+        tokens.append_all(quote! { &[ #(#args),* ] });
     }
 }
 
 impl Signature {
+    /// Replace `Self` in default values by the type of `impl` block
+    pub fn replace_self(&mut self, self_: &Type) {
+        for arg in self.args.iter_mut() {
+            if let SignatureArg::Assign(_, _, value) = arg {
+                replace_self(value, self_);
+            }
+        }
+    }
+
+    /// Names of `*args` and `**kwargs` arguments
+    pub fn variadic_names(&self) -> Vec<String> {
+        self.args
+            .iter()
+            .filter_map(|arg| match arg {
+                SignatureArg::Args(_, ident) | SignatureArg::Keywords(_, _, ident) => {
+                    Some(ident.unraw().to_string())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn overriding_operator(sig: &syn::Signature) -> Option<Self> {
         if sig.ident == "__pow__" {
             return Some(syn::parse_str("(exponent, modulo=None)").unwrap());
@@ -93,3 +271,34 @@ impl Signature {
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_python_literal() -> Result<()> {
+        let literal =
+            |expr: &str| -> Result<Option<String>> { Ok(python_literal(&syn::parse_str(expr)?)) };
+        assert_eq!(literal("30u32")?.as_deref(), Some("30"));
+        assert_eq!(literal("-1.5")?.as_deref(), Some("-1.5"));
+        assert_eq!(literal("true")?.as_deref(), Some("True"));
+        assert_eq!(literal("None")?.as_deref(), Some("None"));
+        assert_eq!(literal(r#""a\"b""#)?.as_deref(), Some(r#""a\"b""#));
+        assert_eq!(literal("vec![1]")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_self() -> Result<()> {
+        let mut sig: Signature = syn::parse_str("(a = Self::North, b = Some(Self::MAX), c = 1)")?;
+        sig.replace_self(&syn::parse_str("Wrapper<u8>")?);
+        let expected: Signature =
+            syn::parse_str("(a = Wrapper::<u8>::North, b = Some(Wrapper::<u8>::MAX), c = 1)")?;
+        assert_eq!(
+            sig.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+        Ok(())
+    }
+}
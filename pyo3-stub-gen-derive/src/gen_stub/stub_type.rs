@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::Type;
+
+/// Implementation of `PyStubType` trait for `#[pyclass]` struct and enum
+pub struct StubType {
+    pub ty: Type,
+    pub name: String,
+    pub module: Option<String>,
+}
+
+impl ToTokens for StubType {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self { ty, name, module } = self;
+        let module_tt = if let Some(module) = module {
+            quote! { #module.into() }
+        } else {
+            quote! { ::pyo3_stub_gen::ModuleRef::Default }
+        };
+        tokens.append_all(quote! {
+            impl ::pyo3_stub_gen::PyStubType for #ty {
+                fn type_output() -> ::pyo3_stub_gen::TypeInfo {
+                    ::pyo3_stub_gen::TypeInfo::locally_defined(#name, #module_tt)
+                }
+            }
+        })
+    }
+}
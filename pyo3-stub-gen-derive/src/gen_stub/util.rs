@@ -55,20 +55,51 @@ pub fn escape_return_type(ret: &ReturnType) -> Option<Type> {
 }
 
 fn unwrap_pyresult(ty: &Type) -> &Type {
+    first_type_arg(ty, &["PyResult"]).unwrap_or(ty)
+}
+
+/// Extract `T` from `Option<T>` or `IterNextOutput<T, U>` returned by `__next__`,
+/// since `None` and `Return(U)` stop the iteration rather than being yielded.
+pub fn unwrap_next_output(ty: &Type) -> &Type {
+    first_type_arg(ty, &["Option", "IterNextOutput", "IterANextOutput"]).unwrap_or(ty)
+}
+
+/// Whether the type is `Self`, `PyRef<Self>`, `PyRefMut<Self>` or `Py<Self>`
+pub fn is_self_type(ty: &Type) -> bool {
+    let ty = first_type_arg(ty, &["PyRef", "PyRefMut", "Py"]).unwrap_or(ty);
+    matches!(ty, Type::Path(TypePath { qself: None, path }) if path.is_ident("Self"))
+}
+
+/// The first type argument of generic types named `names`, e.g. `T` of `Option<T>`
+fn first_type_arg<'a>(ty: &'a Type, names: &[&str]) -> Option<&'a Type> {
     if let Type::Path(TypePath { path, .. }) = ty {
         if let Some(last) = path.segments.last() {
-            if last.ident == "PyResult" {
+            if names.iter().any(|name| last.ident == name) {
                 if let PathArguments::AngleBracketed(inner) = &last.arguments {
                     for arg in &inner.args {
                         if let GenericArgument::Type(ty) = arg {
-                            return ty;
+                            return Some(ty);
                         }
                     }
                 }
             }
         }
     }
-    ty
+    None
+}
+
+/// Format the token stream of `*Info` struct as a Rust expression for snapshot tests
+#[cfg(test)]
+pub fn format_as_value(tt: TokenStream2) -> String {
+    let ttt = quote! { const _: () = #tt; };
+    let formatted = prettyplease::unparse(&syn::parse_file(&ttt.to_string()).unwrap());
+    formatted
+        .trim()
+        .strip_prefix("const _: () = ")
+        .unwrap()
+        .strip_suffix(';')
+        .unwrap()
+        .to_string()
 }
 
 #[cfg(test)]
@@ -76,6 +107,16 @@ mod test {
     use super::*;
     use syn::{parse_str, Result};
 
+    #[test]
+    fn test_is_self_type() -> Result<()> {
+        assert!(is_self_type(&parse_str("Self")?));
+        assert!(is_self_type(&parse_str("PyRef<'_, Self>")?));
+        assert!(is_self_type(&parse_str("Py<Self>")?));
+        assert!(!is_self_type(&parse_str("Py<ItemsIter>")?));
+        assert!(!is_self_type(&parse_str("Vec<Self>")?));
+        Ok(())
+    }
+
     #[test]
     fn test_unwrap_pyresult() -> Result<()> {
         let ty: Type = parse_str("PyResult<i32>")?;
@@ -104,4 +145,17 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_unwrap_next_output() -> Result<()> {
+        let ty: Type = parse_str("Option<usize>")?;
+        assert_eq!(unwrap_next_output(&ty), &parse_str("usize")?);
+
+        let ty: Type = parse_str("IterNextOutput<PyObject, &str>")?;
+        assert_eq!(unwrap_next_output(&ty), &parse_str("PyObject")?);
+
+        let ty: Type = parse_str("usize")?;
+        assert_eq!(unwrap_next_output(&ty), &ty);
+        Ok(())
+    }
 }
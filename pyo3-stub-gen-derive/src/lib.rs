@@ -20,6 +20,9 @@ use proc_macro::TokenStream;
 ///     pub custom_latex: Option<String>,
 /// }
 /// ```
+///
+/// The type of `#[pyo3(get)]` field can be overridden by `#[gen_stub(override_type(...))]`
+/// as same as arguments of [macro@gen_stub_pyfunction].
 #[proc_macro_attribute]
 pub fn gen_stub_pyclass(_attr: TokenStream, item: TokenStream) -> TokenStream {
     gen_stub::pyclass(item.into())
@@ -57,6 +60,7 @@ pub fn gen_stub_pyclass_enum(_attr: TokenStream, item: TokenStream) -> TokenStre
 /// # #[gen_stub_pyclass]
 /// # #[pyclass]
 /// # struct PyAddOp {}
+/// # #[gen_stub_pyclass]
 /// # #[pyclass]
 /// # struct Expression {}
 /// #[gen_stub_pymethods]
@@ -68,6 +72,48 @@ pub fn gen_stub_pyclass_enum(_attr: TokenStream, item: TokenStream) -> TokenStre
 ///     }
 /// }
 /// ```
+///
+/// `#[pymethods]` of enums annotated by [macro@gen_stub_pyclass_enum] is also supported.
+///
+/// `__richcmp__` is expanded into `__eq__`, `__ne__`, `__lt__`, `__le__`, `__gt__` and `__ge__`.
+/// The operators can be restricted by `#[gen_stub(richcmp(...))]`.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// # #[gen_stub_pyclass]
+/// # #[pyclass]
+/// # struct Version {}
+/// #[gen_stub_pymethods]
+/// #[pymethods]
+/// impl Version {
+///     #[gen_stub(richcmp(lt, le, gt, ge))]
+///     fn __richcmp__(&self, other: PyRef<'_, Self>, op: pyo3::pyclass::CompareOp) -> bool {
+///         todo!()
+///     }
+/// }
+/// ```
+///
+/// `#[gen_stub(overload = "...")]` and `#[gen_stub(awaitable(T))]` are also available for methods
+/// as same as [macro@gen_stub_pyfunction]. Overloads of methods must take `self`, or `cls` for `#[classmethod]`,
+/// as the first parameter, while those of `#[staticmethod]` must not.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// # #[gen_stub_pyclass]
+/// # #[pyclass]
+/// # struct Vector {}
+/// #[gen_stub_pymethods]
+/// #[pymethods]
+/// impl Vector {
+///     #[gen_stub(overload = "def scale(self, factor: float) -> Vector")]
+///     #[gen_stub(overload = "def scale(self, factor: Vector) -> float")]
+///     fn scale(&self, factor: &PyAny) -> PyResult<PyObject> {
+///         todo!()
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn gen_stub_pymethods(_attr: TokenStream, item: TokenStream) -> TokenStream {
     gen_stub::pymethods(item.into())
@@ -80,6 +126,7 @@ pub fn gen_stub_pymethods(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// ```
 /// # use pyo3_stub_gen_derive::*;
 /// # use pyo3::*;
+/// # #[gen_stub_pyclass]
 /// # #[pyclass]
 /// # #[derive(Clone)]
 /// # pub struct Expression {}
@@ -97,6 +144,7 @@ pub fn gen_stub_pymethods(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// ```
 /// # use pyo3_stub_gen_derive::*;
 /// # use pyo3::*;
+/// # #[gen_stub_pyclass]
 /// # #[pyclass]
 /// # #[derive(Clone)]
 /// # pub struct Expression {}
@@ -107,6 +155,68 @@ pub fn gen_stub_pymethods(_attr: TokenStream, item: TokenStream) -> TokenStream
 ///     todo!()
 /// }
 /// ```
+///
+/// The Python type of arguments and return value can be overridden by `#[gen_stub(...)]` attributes
+/// when the Rust type does not express it well. Modules listed in `imports` are imported as `import {module}`.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// #[gen_stub_pyfunction]
+/// #[pyfunction]
+/// #[gen_stub(override_return_type(type_repr = "collections.abc.Callable[[int], str]", imports = ("collections.abc",)))]
+/// pub fn make_formatter(
+///     #[gen_stub(override_type(type_repr = "typing.SupportsFloat", imports = ("typing",)))] scale: &PyAny,
+/// ) -> PyObject {
+///     todo!()
+/// }
+/// ```
+///
+/// Functions accepting several Python types can be declared by `#[gen_stub(overload = "...")]`,
+/// which can be repeated. Only these signatures are written in the stub file with `@overload`,
+/// and the docstring is placed on the last one.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// /// Doubles a number, or repeats a string twice
+/// #[gen_stub_pyfunction]
+/// #[pyfunction]
+/// #[gen_stub(overload = "def double(x: int) -> int")]
+/// #[gen_stub(overload = "def double(x: str) -> str")]
+/// pub fn double(x: &PyAny) -> PyResult<&PyAny> {
+///     todo!()
+/// }
+/// ```
+///
+/// Types used in the signature are not imported automatically, since the Rust types of the function are not used.
+/// Use `overload(signature = "...", imports = (...))` to import modules as `import {module}`.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// #[gen_stub_pyfunction]
+/// #[pyfunction]
+/// #[gen_stub(overload(signature = "def first(x: collections.abc.Sequence[int]) -> int", imports = ("collections.abc",)))]
+/// #[gen_stub(overload = "def first(x: str) -> str")]
+/// pub fn first(x: &PyAny) -> PyResult<&PyAny> {
+///     todo!()
+/// }
+/// ```
+///
+/// Functions returning a Python awaitable, e.g. created by `pyo3-asyncio`, are annotated as
+/// `collections.abc.Awaitable[T]` by `#[gen_stub(awaitable(T))]` where `T` is the Rust type of the result.
+///
+/// ```
+/// # use pyo3_stub_gen_derive::*;
+/// # use pyo3::*;
+/// #[gen_stub_pyfunction]
+/// #[pyfunction]
+/// #[gen_stub(awaitable(usize))]
+/// pub fn fetch_count(py: Python<'_>) -> PyResult<&PyAny> {
+///     todo!()
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn gen_stub_pyfunction(attr: TokenStream, item: TokenStream) -> TokenStream {
     gen_stub::pyfunction(attr.into(), item.into())
@@ -1,9 +1,6 @@
 # This file is automatically generated by pyo3_stub_gen
 
-from typing import final, Any, List, Dict
-from enum import Enum, auto
-
-def sum_as_string(a:int,b:int) -> str:
+def sum_as_string(a: int, b: int) -> str:
     r"""
     Returns the sum of two numbers as a string.
     """
@@ -1,11 +1,185 @@
 # This file is automatically generated by pyo3_stub_gen
 
-from typing import final, Any, List, Dict
+import collections.abc
+from collections.abc import Awaitable, Sequence
 from enum import Enum, auto
+from typing import Final, final, overload
+from typing_extensions import Self
 
-def sum_as_string(a:int,b:int) -> str:
+VERSION: Final[str]
+
+@final
+class Countdown:
+    r"""
+    Iterates from `start` down to one
+    """
+    def __new__(cls, start: int): ...
+    def __len__(self) -> int:
+        ...
+
+    def __iter__(self) -> Self:
+        ...
+
+    def __next__(self) -> int:
+        ...
+
+
+@final
+class Counter:
+    r"""
+    Counts up by `step`, which can be changed at any time.
+    """
+    DEFAULT_STEP: Final[int]
+    MAX_STEP: Final[int]
+    IDLE: Final[Counter]
+    @property
+    def total(self) -> int: ...
+    @property
+    def step(self) -> int: ...
+    @step.setter
+    def step(self, value: int) -> None: ...
+    def __new__(cls, step: int = 1): ...
+    def increment(self) -> int:
+        r"""
+        Adds `step` to the total and returns it
+        """
+        ...
+
+
+class Shape:
+    r"""
+    Base class of shapes, which can be inherited in Python
+    """
+    def __new__(cls): ...
+
+@final
+class Rectangle(Shape):
+    r"""
+    Rectangle defined by its width and height
+    """
+    @property
+    def width(self) -> float: ...
+    @property
+    def height(self) -> float: ...
+    def __new__(cls, width: float, height: float): ...
+    def area(self) -> float:
+        ...
+
+    def __lt__(self, other: Rectangle) -> bool:
+        r"""
+        Compares rectangles by their areas
+        """
+        ...
+
+    def __le__(self, other: Rectangle) -> bool:
+        r"""
+        Compares rectangles by their areas
+        """
+        ...
+
+    def __gt__(self, other: Rectangle) -> bool:
+        r"""
+        Compares rectangles by their areas
+        """
+        ...
+
+    def __ge__(self, other: Rectangle) -> bool:
+        r"""
+        Compares rectangles by their areas
+        """
+        ...
+
+
+@final
+class Direction(Enum):
+    r"""
+    Compass direction
+    """
+    North = auto()
+    East = auto()
+    South = auto()
+    West = auto()
+    def opposite(self) -> Direction:
+        r"""
+        Direction turned by 180 degrees
+        """
+        ...
+
+    def is_opposite(self, other: Direction = Direction.North) -> bool:
+        r"""
+        Whether the direction is the opposite of the given one
+        """
+        ...
+
+
+@final
+class LengthUnit(Enum):
+    r"""
+    Unit of length
+    """
+    METER = auto()
+    FOOT = auto()
+
+def call_with(f: collections.abc.Callable[[int], str], x: int) -> str:
+    r"""
+    Calls the given callable with `x` and returns its result.
+    """
+    ...
+
+def count_words(words: Sequence[str]) -> dict[str, int]:
+    r"""
+    Counts occurrences of each word
+    """
+    ...
+
+def delayed(delay: float, value: int) -> Awaitable[int]:
+    r"""
+    Returns an awaitable resolving to `value` after `delay` seconds
+    """
+    ...
+
+@overload
+def double(x: int) -> int: ...
+@overload
+def double(x: str) -> str:
+    r"""
+    Doubles a number, or repeats a string twice
+    """
+    ...
+
+def greet(name: str | None = None) -> str:
+    r"""
+    Greets `name`, or the world if omitted.
+    """
+    ...
+
+def parse_int(text: str) -> int:
+    r"""
+    Parses an integer, raising `ParseError` for invalid input.
+    """
+    ...
+
+def repeat(text: str, times: int = 2, sep: str = ", ") -> str:
+    r"""
+    Repeats `text` with `sep`, e.g. `repeat("a", 3)` returns `"a, a, a"`.
+    """
+    ...
+
+def sum_as_string(a: int, b: int) -> str:
     r"""
     Returns the sum of two numbers as a string.
     """
     ...
 
+def to_meters(value: float, unit: LengthUnit = LengthUnit.METER) -> float:
+    r"""
+    Converts the length in `unit` into meters
+    """
+    ...
+
+class ParseError(ValueError):
+    r"""
+    Raised when the input cannot be parsed
+    """
+    ...
+
@@ -1,9 +1,12 @@
+// PyO3 0.20 expands `#[new]` into impls inside a function, which recent rustc warns about
+#![allow(non_local_definitions)]
+
 #[cfg_attr(target_os = "macos", doc = include_str!("../../README.md"))]
 mod readme {}
 
-use pyo3::prelude::*;
-use pyo3_stub_gen::{derive::gen_stub_pyfunction, StubInfo};
-use std::{env, path::*};
+use pyo3::{exceptions::PyValueError, prelude::*, pyclass::CompareOp};
+use pyo3_stub_gen::{derive::*, StubInfo};
+use std::{collections::HashMap, env, path::*};
 
 /// Gather information to generate stub files
 pub fn stub_info() -> pyo3_stub_gen::Result<StubInfo> {
@@ -18,10 +21,270 @@ fn sum_as_string(a: usize, b: usize) -> PyResult<String> {
     Ok((a + b).to_string())
 }
 
+/// Calls the given callable with `x` and returns its result.
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[gen_stub(override_return_type(type_repr = "str"))]
+fn call_with(
+    py: Python<'_>,
+    #[gen_stub(override_type(
+        type_repr = "collections.abc.Callable[[int], str]",
+        imports = ("collections.abc",)
+    ))]
+    f: PyObject,
+    x: usize,
+) -> PyResult<PyObject> {
+    f.call1(py, (x,))
+}
+
+/// Repeats `text` with `sep`, e.g. `repeat("a", 3)` returns `"a, a, a"`.
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(signature = (text, times = 2, sep = ", "))]
+fn repeat(text: &str, times: usize, sep: &str) -> String {
+    vec![text; times].join(sep)
+}
+
+/// Greets `name`, or the world if omitted.
+#[gen_stub_pyfunction]
+#[pyfunction]
+fn greet(name: Option<&str>) -> String {
+    format!("Hello, {}!", name.unwrap_or("world"))
+}
+
+/// Counts occurrences of each word
+#[gen_stub_pyfunction]
+#[pyfunction]
+fn count_words(words: Vec<String>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in words {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Doubles a number, or repeats a string twice
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[gen_stub(overload = "def double(x: int) -> int")]
+#[gen_stub(overload = "def double(x: str) -> str")]
+fn double(x: &PyAny) -> PyResult<&PyAny> {
+    x.call_method1("__add__", (x,))
+}
+
+/// Returns an awaitable resolving to `value` after `delay` seconds
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[gen_stub(awaitable(usize))]
+fn delayed(py: Python<'_>, delay: f64, value: usize) -> PyResult<&PyAny> {
+    py.import("asyncio")?.call_method1("sleep", (delay, value))
+}
+
+/// Counts up by `step`, which can be changed at any time.
+#[gen_stub_pyclass]
+#[pyclass]
+struct Counter {
+    #[pyo3(get)]
+    total: usize,
+    #[pyo3(get, set)]
+    step: usize,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Counter {
+    #[classattr]
+    #[pyo3(name = "DEFAULT_STEP")]
+    fn default_step() -> usize {
+        1
+    }
+
+    #[classattr]
+    const MAX_STEP: usize = 100;
+
+    /// Counter which never counts up
+    #[classattr]
+    #[pyo3(name = "IDLE")]
+    fn idle() -> Self {
+        Self { total: 0, step: 0 }
+    }
+
+    #[new]
+    #[pyo3(signature = (step = 1))]
+    fn new(step: usize) -> Self {
+        Self { total: 0, step }
+    }
+
+    /// Adds `step` to the total and returns it
+    fn increment(&mut self) -> usize {
+        self.total += self.step;
+        self.total
+    }
+}
+
+/// Iterates from `start` down to one
+#[gen_stub_pyclass]
+#[pyclass]
+struct Countdown {
+    current: usize,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Countdown {
+    #[new]
+    fn new(start: usize) -> Self {
+        Self { current: start }
+    }
+
+    fn __len__(&self) -> usize {
+        self.current
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<usize> {
+        let current = self.current;
+        self.current = current.checked_sub(1)?;
+        Some(current)
+    }
+}
+
+/// Base class of shapes, which can be inherited in Python
+#[gen_stub_pyclass]
+#[pyclass(subclass)]
+struct Shape {}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Shape {
+    #[new]
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Rectangle defined by its width and height
+#[gen_stub_pyclass]
+#[pyclass(extends = Shape)]
+struct Rectangle {
+    #[pyo3(get)]
+    width: f64,
+    #[pyo3(get)]
+    height: f64,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Rectangle {
+    #[new]
+    fn new(width: f64, height: f64) -> (Self, Shape) {
+        (Self { width, height }, Shape::new())
+    }
+
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Compares rectangles by their areas
+    #[gen_stub(richcmp(lt, le, gt, ge))]
+    fn __richcmp__(&self, other: PyRef<'_, Self>, op: CompareOp) -> bool {
+        op.matches(self.area().total_cmp(&other.area()))
+    }
+}
+
+/// Compass direction
+#[gen_stub_pyclass_enum]
+#[pyclass]
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Direction {
+    /// Direction turned by 180 degrees
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Whether the direction is the opposite of the given one
+    #[pyo3(signature = (other = Self::North))]
+    fn is_opposite(&self, other: Direction) -> bool {
+        self.opposite() == other
+    }
+}
+
+/// Unit of length
+#[gen_stub_pyclass_enum]
+#[pyclass]
+#[derive(Clone, Copy)]
+enum LengthUnit {
+    #[pyo3(name = "METER")]
+    Meter,
+    #[pyo3(name = "FOOT")]
+    Foot,
+}
+
+/// Converts the length in `unit` into meters
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(signature = (value, unit = LengthUnit::Meter))]
+fn to_meters(value: f64, unit: LengthUnit) -> f64 {
+    match unit {
+        LengthUnit::Meter => value,
+        LengthUnit::Foot => value * 0.3048,
+    }
+}
+
+pyo3_stub_gen::create_exception!(
+    pyo3_stub_gen_testing_pure,
+    ParseError,
+    PyValueError,
+    "Raised when the input cannot be parsed"
+);
+
+/// Parses an integer, raising `ParseError` for invalid input.
+#[gen_stub_pyfunction]
+#[pyfunction]
+fn parse_int(text: &str) -> PyResult<i64> {
+    text.parse()
+        .map_err(|err: std::num::ParseIntError| ParseError::new_err(err.to_string()))
+}
+
+pyo3_stub_gen::module_variable!(pyo3_stub_gen_testing_pure, VERSION: Final<&str>);
+
 /// Initializes the Python module
 #[pymodule]
-fn pyo3_stub_gen_testing_pure(_py: Python, m: &PyModule) -> PyResult<()> {
+fn pyo3_stub_gen_testing_pure(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(sum_as_string, m)?)?;
+    m.add_function(wrap_pyfunction!(call_with, m)?)?;
+    m.add_function(wrap_pyfunction!(repeat, m)?)?;
+    m.add_function(wrap_pyfunction!(greet, m)?)?;
+    m.add_function(wrap_pyfunction!(count_words, m)?)?;
+    m.add_function(wrap_pyfunction!(double, m)?)?;
+    m.add_function(wrap_pyfunction!(delayed, m)?)?;
+    m.add_class::<Counter>()?;
+    m.add_class::<Countdown>()?;
+    m.add_class::<Shape>()?;
+    m.add_class::<Rectangle>()?;
+    m.add_class::<Direction>()?;
+    m.add_class::<LengthUnit>()?;
+    m.add_function(wrap_pyfunction!(to_meters, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_int, m)?)?;
+    m.add("ParseError", py.get_type::<ParseError>())?;
+    m.add("VERSION", env!("CARGO_PKG_VERSION"))?;
     Ok(())
 }
 
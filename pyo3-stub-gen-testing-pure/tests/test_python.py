@@ -1,5 +1,83 @@
+import asyncio
+
 import pyo3_stub_gen_testing_pure
+import pytest
 
 
 def test_sum_as_string():
     assert pyo3_stub_gen_testing_pure.sum_as_string(1, 2) == "3"
+
+
+def test_call_with():
+    assert pyo3_stub_gen_testing_pure.call_with(str, 3) == "3"
+
+
+def test_repeat():
+    assert pyo3_stub_gen_testing_pure.repeat("a") == "a, a"
+    assert pyo3_stub_gen_testing_pure.repeat("a", 3, sep="-") == "a-a-a"
+
+
+def test_greet():
+    assert pyo3_stub_gen_testing_pure.greet() == "Hello, world!"
+    assert pyo3_stub_gen_testing_pure.greet("Rust") == "Hello, Rust!"
+
+
+def test_count_words():
+    assert pyo3_stub_gen_testing_pure.count_words(("a", "b", "a")) == {"a": 2, "b": 1}
+
+
+def test_double():
+    assert pyo3_stub_gen_testing_pure.double(2) == 4
+    assert pyo3_stub_gen_testing_pure.double("ab") == "abab"
+
+
+def test_delayed():
+    assert asyncio.run(pyo3_stub_gen_testing_pure.delayed(0.0, 3)) == 3
+
+
+def test_countdown():
+    countdown = pyo3_stub_gen_testing_pure.Countdown(3)
+    assert len(countdown) == 3
+    assert list(countdown) == [3, 2, 1]
+
+
+def test_counter():
+    counter = pyo3_stub_gen_testing_pure.Counter(2)
+    assert counter.increment() == 2
+    counter.step = 3
+    assert counter.increment() == 5
+    assert counter.total == 5
+    assert pyo3_stub_gen_testing_pure.Counter().step == pyo3_stub_gen_testing_pure.Counter.DEFAULT_STEP
+    assert pyo3_stub_gen_testing_pure.Counter.MAX_STEP == 100
+    assert pyo3_stub_gen_testing_pure.Counter.IDLE.step == 0
+
+
+def test_rectangle():
+    rectangle = pyo3_stub_gen_testing_pure.Rectangle(2.0, 3.0)
+    assert isinstance(rectangle, pyo3_stub_gen_testing_pure.Shape)
+    assert rectangle.area() == 6.0
+    assert rectangle < pyo3_stub_gen_testing_pure.Rectangle(3.0, 3.0)
+
+
+def test_direction():
+    Direction = pyo3_stub_gen_testing_pure.Direction
+    assert Direction.North.opposite() == Direction.South
+    assert Direction.South.is_opposite()
+
+
+def test_to_meters():
+    LengthUnit = pyo3_stub_gen_testing_pure.LengthUnit
+    assert pyo3_stub_gen_testing_pure.to_meters(2.0) == 2.0
+    assert pyo3_stub_gen_testing_pure.to_meters(10.0, LengthUnit.FOOT) == 3.048
+
+
+def test_parse_int():
+    assert pyo3_stub_gen_testing_pure.parse_int("42") == 42
+    with pytest.raises(ValueError):
+        pyo3_stub_gen_testing_pure.parse_int("x")
+    with pytest.raises(pyo3_stub_gen_testing_pure.ParseError):
+        pyo3_stub_gen_testing_pure.parse_int("x")
+
+
+def test_version():
+    assert isinstance(pyo3_stub_gen_testing_pure.VERSION, str)
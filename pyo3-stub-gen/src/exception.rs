@@ -0,0 +1,34 @@
+/// Wrapper of [pyo3::create_exception] which also embeds metadata for stub file generation
+///
+/// ```
+/// use pyo3::exceptions::PyValueError;
+///
+/// pyo3_stub_gen::create_exception!(my_module, ParseError, PyValueError, "Invalid input");
+/// ```
+///
+/// This generates `class ParseError(ValueError)` in the stub file of `my_module`.
+/// The created exception can be a base of another one since [crate::PyStubType] is implemented for it.
+#[macro_export]
+macro_rules! create_exception {
+    ($module: expr, $name: ident, $base: ty) => {
+        $crate::create_exception!($module, $name, $base, "");
+    };
+    ($module: expr, $name: ident, $base: ty, $doc: expr) => {
+        ::pyo3::create_exception!($module, $name, $base, $doc);
+
+        impl $crate::PyStubType for $name {
+            fn type_output() -> $crate::TypeInfo {
+                $crate::TypeInfo::locally_defined(stringify!($name), stringify!($module).into())
+            }
+        }
+
+        $crate::inventory::submit! {
+            $crate::type_info::PyErrorInfo {
+                name: stringify!($name),
+                module: stringify!($module),
+                base: <$base as $crate::PyStubType>::type_output,
+                doc: $doc,
+            }
+        }
+    };
+}
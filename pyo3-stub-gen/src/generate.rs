@@ -1,10 +1,9 @@
 //! Generate Python typing stub file a.k.a. `*.pyi` file.
 
-use crate::{pyproject::PyProject, type_info::*};
+use crate::{pyproject::PyProject, type_info::*, ImportRef, ModuleRef, TypeInfo};
 
 use anyhow::{anyhow, bail, Result};
 use itertools::Itertools;
-use pyo3::inspect::types::TypeInfo;
 use std::{
     any::TypeId,
     collections::{BTreeMap, BTreeSet},
@@ -17,10 +16,17 @@ fn indent() -> &'static str {
     "    "
 }
 
+/// Imports required by each part of the stub file
+trait Import {
+    fn import(&self) -> BTreeSet<ImportRef>;
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct Arg {
     name: &'static str,
     r#type: TypeInfo,
+    /// Default value in `#[pyo3(signature = (...))]`, e.g. `1` for `a = 1`
+    default: Option<TypeInfo>,
 }
 
 impl Arg {
@@ -28,13 +34,124 @@ impl Arg {
         Self {
             name: info.name,
             r#type: (info.r#type)(),
+            default: info.is_optional.then(TypeInfo::none),
         }
     }
 }
 
+impl Import for Arg {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = self.r#type.import.clone();
+        if let Some(default) = &self.default {
+            import.extend(default.import.iter().cloned());
+        }
+        import
+    }
+}
+
 impl fmt::Display for Arg {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}:{}", self.name, self.r#type)
+        write!(f, "{}: {}", self.name, self.r#type)?;
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+        Ok(())
+    }
+}
+
+/// Component of the parameter list of Python functions
+#[derive(Debug, Clone, PartialEq)]
+enum Parameter {
+    /// `a: int` or `a: int = ...`
+    Arg(Arg),
+    /// `*args: Any`
+    VarArgs(Arg),
+    /// `**kwargs: Any`
+    VarKeywords(Arg),
+    /// `*`, the following arguments are keyword-only
+    Star,
+    /// `/`, the preceding arguments are positional-only
+    Slash,
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Parameter::Arg(arg) => write!(f, "{}", arg),
+            Parameter::VarArgs(arg) => write!(f, "*{}", arg),
+            Parameter::VarKeywords(arg) => write!(f, "**{}", arg),
+            Parameter::Star => write!(f, "*"),
+            Parameter::Slash => write!(f, "/"),
+        }
+    }
+}
+
+/// Parameter list of Python functions, without `self` or `cls`
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Parameters(Vec<Parameter>);
+
+impl Parameters {
+    /// Merge Rust arguments with `#[pyo3(signature = (...))]` if exists
+    fn new(args: &[ArgInfo], signature: Option<&[SignatureArg]>) -> Self {
+        let args: Vec<Arg> = args.iter().map(Arg::from_info).collect();
+        let Some(signature) = signature else {
+            return Self(args.into_iter().map(Parameter::Arg).collect());
+        };
+
+        // PyO3 requires the signature to list the Rust arguments in the same order,
+        // but names may differ e.g. for `__pow__` where the signature is provided by this crate.
+        let mut position = 0;
+        let mut lookup = |name: &'static str| -> Arg {
+            let found = args
+                .iter()
+                .find(|arg| arg.name == name)
+                .or_else(|| args.get(position));
+            position += 1;
+            Arg {
+                name,
+                r#type: found.map_or_else(TypeInfo::any, |arg| arg.r#type.clone()),
+                default: None,
+            }
+        };
+        let parameters = signature
+            .iter()
+            .map(|arg| match arg {
+                SignatureArg::Ident(name) => Parameter::Arg(lookup(name)),
+                SignatureArg::Assign(name, default) => Parameter::Arg(Arg {
+                    default: Some(default()),
+                    ..lookup(name)
+                }),
+                SignatureArg::Star => Parameter::Star,
+                SignatureArg::Slash => Parameter::Slash,
+                SignatureArg::Args(name) => Parameter::VarArgs(lookup(name)),
+                SignatureArg::Keywords(name) => Parameter::VarKeywords(lookup(name)),
+            })
+            .collect();
+        Self(parameters)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Import for Parameters {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = BTreeSet::new();
+        for parameter in &self.0 {
+            if let Parameter::Arg(arg) | Parameter::VarArgs(arg) | Parameter::VarKeywords(arg) =
+                parameter
+            {
+                import.extend(arg.import());
+            }
+        }
+        import
+    }
+}
+
+impl fmt::Display for Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.iter().join(", "))
     }
 }
 
@@ -45,10 +162,13 @@ struct ReturnTypeInfo {
 
 impl fmt::Display for ReturnTypeInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !matches!(self.r#type, TypeInfo::NoReturn) {
-            write!(f, " -> {}", self.r#type)?;
-        }
-        Ok(())
+        write!(f, " -> {}", self.r#type)
+    }
+}
+
+impl Import for ReturnTypeInfo {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        self.r#type.import.clone()
     }
 }
 
@@ -61,58 +181,157 @@ impl From<TypeInfo> for ReturnTypeInfo {
 #[derive(Debug, Clone, PartialEq)]
 struct MethodDef {
     name: &'static str,
-    args: Vec<Arg>,
-    signature: Option<&'static str>,
+    parameters: Parameters,
     r#return: ReturnTypeInfo,
     doc: &'static str,
     is_static: bool,
     is_class: bool,
+    is_async: bool,
+    overloads: &'static [OverloadInfo],
 }
 
 impl MethodDef {
     fn from_info(info: &MethodInfo) -> Self {
-        Self {
+        let mut method = Self {
             name: info.name,
-            args: info.args.iter().map(Arg::from_info).collect(),
-            signature: info.signature,
+            parameters: Parameters::new(info.args, info.signature),
             r#return: (info.r#return)().into(),
             doc: info.doc,
             is_static: info.is_static,
             is_class: info.is_class,
+            is_async: info.is_async,
+            overloads: info.overloads,
+        };
+        if !info.is_return_overridden {
+            method.apply_protocol();
+        }
+        method
+    }
+
+    /// Fix signatures of special methods to match Python protocols,
+    /// since Rust types returned from PyO3 protocol slots are converted by PyO3 itself.
+    fn apply_protocol(&mut self) {
+        let r#return = match self.name {
+            "__len__" | "__hash__" | "__index__" | "__int__" => TypeInfo::builtin("int"),
+            "__bool__" | "__contains__" => TypeInfo::builtin("bool"),
+            "__repr__" | "__str__" => TypeInfo::builtin("str"),
+            "__float__" => TypeInfo::builtin("float"),
+            "__complex__" => TypeInfo::builtin("complex"),
+            "__setitem__" | "__delitem__" | "__setattr__" | "__delattr__" | "__set__"
+            | "__delete__" => TypeInfo::none(),
+            _ => return self.apply_reflected_operand(),
+        };
+        self.r#return = r#return.into();
+    }
+
+    /// Reflected operators receive any object as the left operand,
+    /// and PyO3 returns `NotImplemented` if it cannot be extracted.
+    fn apply_reflected_operand(&mut self) {
+        const REFLECTED: &[&str] = &[
+            "__radd__",
+            "__rsub__",
+            "__rmul__",
+            "__rmatmul__",
+            "__rtruediv__",
+            "__rfloordiv__",
+            "__rmod__",
+            "__rdivmod__",
+            "__rpow__",
+            "__rlshift__",
+            "__rrshift__",
+            "__rand__",
+            "__rxor__",
+            "__ror__",
+        ];
+        if !REFLECTED.contains(&self.name) {
+            return;
+        }
+        if let Some(Parameter::Arg(operand)) = self.parameters.0.first_mut() {
+            operand.r#type = TypeInfo::builtin("object");
+        }
+    }
+
+    /// Method generated by PyO3 itself, e.g. `__eq__` by `#[pyclass(eq)]`
+    fn synthesized(
+        name: &'static str,
+        args: Vec<(&'static str, TypeInfo)>,
+        r#return: TypeInfo,
+    ) -> Self {
+        let args = args
+            .into_iter()
+            .map(|(name, r#type)| {
+                Parameter::Arg(Arg {
+                    name,
+                    r#type,
+                    default: None,
+                })
+            })
+            .collect();
+        Self {
+            name,
+            parameters: Parameters(args),
+            r#return: r#return.into(),
+            doc: "",
+            is_static: false,
+            is_class: false,
+            is_async: false,
+            overloads: &[],
         }
     }
 }
 
+impl Import for MethodDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        // Rust types are not rendered if overloads are given
+        if !self.overloads.is_empty() {
+            return overload_import(self.overloads);
+        }
+        let mut import = self.r#return.import();
+        import.extend(self.parameters.import());
+        import
+    }
+}
+
 impl fmt::Display for MethodDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let indent = indent();
-        let mut needs_comma = false;
-        if self.is_static {
-            writeln!(f, "{indent}@staticmethod")?;
-            write!(f, "{indent}def {}(", self.name)?;
+        let mut params = Vec::new();
+        let decorator = if self.is_static {
+            Some("@staticmethod")
         } else if self.is_class {
-            writeln!(f, "{indent}@classmethod")?;
-            write!(f, "{indent}def {}(cls", self.name)?;
-            needs_comma = true;
+            params.push("cls".to_string());
+            Some("@classmethod")
         } else {
-            write!(f, "{indent}def {}(self", self.name)?;
-            needs_comma = true;
-        }
-        if let Some(signature) = self.signature {
-            if needs_comma {
-                write!(f, ", ")?;
+            params.push("self".to_string());
+            None
+        };
+        let (last, overloads) = match self.overloads.split_last() {
+            Some((last, overloads)) => (Some(last), overloads),
+            None => (None, self.overloads),
+        };
+        for overload in overloads {
+            writeln!(f, "{indent}@overload")?;
+            if let Some(decorator) = decorator {
+                writeln!(f, "{indent}{decorator}")?;
             }
-            write!(f, "{}", signature)?;
+            writeln!(f, "{indent}{}: ...", overload_signature(overload))?;
+        }
+        if last.is_some() {
+            writeln!(f, "{indent}@overload")?;
+        }
+        if let Some(decorator) = decorator {
+            writeln!(f, "{indent}{decorator}")?;
+        }
+        if let Some(last) = last {
+            writeln!(f, "{indent}{}:", overload_signature(last))?;
         } else {
-            for arg in &self.args {
-                if needs_comma {
-                    write!(f, ", ")?;
-                }
-                write!(f, "{}", arg)?;
-                needs_comma = true;
+            if !self.parameters.is_empty() {
+                params.push(self.parameters.to_string());
             }
+            let r#async = if self.is_async { "async " } else { "" };
+            write!(f, "{indent}{async}def {}({}", self.name, params.join(", "))?;
+            writeln!(f, "){}:", self.r#return)?;
         }
-        writeln!(f, "){}:", self.r#return)?;
 
         let doc = self.doc;
         if !doc.is_empty() {
@@ -131,54 +350,65 @@ impl fmt::Display for MethodDef {
 #[derive(Debug, Clone, PartialEq)]
 struct MemberDef {
     name: &'static str,
-    r#type: TypeInfo,
+    /// Type returned by the getter
+    getter: Option<TypeInfo>,
+    /// Type accepted by the setter
+    setter: Option<TypeInfo>,
 }
 
-impl MemberDef {
-    fn from_info(info: &MemberInfo) -> Self {
-        Self {
-            name: info.name,
-            r#type: (info.r#type)(),
+impl Import for MemberDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = BTreeSet::new();
+        for ty in self.getter.iter().chain(&self.setter) {
+            import.extend(ty.import.iter().cloned());
         }
+        import
     }
 }
 
 impl fmt::Display for MemberDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let indent = indent();
-        writeln!(f, "{indent}{}: {}", self.name, self.r#type)
+        let name = self.name;
+        // Write-only members still need a getter to declare the property
+        let Some(getter) = self.getter.as_ref().or(self.setter.as_ref()) else {
+            return Ok(());
+        };
+        writeln!(f, "{indent}@property")?;
+        writeln!(f, "{indent}def {name}(self) -> {getter}: ...")?;
+        if let Some(setter) = &self.setter {
+            writeln!(f, "{indent}@{name}.setter")?;
+            writeln!(f, "{indent}def {name}(self, value: {setter}) -> None: ...")?;
+        }
+        Ok(())
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 struct NewDef {
-    args: Vec<Arg>,
-    signature: Option<&'static str>,
+    parameters: Parameters,
 }
 
 impl NewDef {
     fn from_info(info: &NewInfo) -> Self {
         Self {
-            args: info.args.iter().map(Arg::from_info).collect(),
-            signature: info.signature,
+            parameters: Parameters::new(info.args, info.signature),
         }
     }
 }
 
+impl Import for NewDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        self.parameters.import()
+    }
+}
+
 impl fmt::Display for NewDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let indent = indent();
-        write!(f, "{indent}def __new__(cls,")?;
-        if let Some(signature) = self.signature {
-            let joined = signature.replace('\n', " ");
-            write!(f, "{}", joined)?;
-        } else {
-            for (n, arg) in self.args.iter().enumerate() {
-                write!(f, "{}", arg)?;
-                if n != self.args.len() - 1 {
-                    write!(f, ", ")?;
-                }
-            }
+        write!(f, "{indent}def __new__(cls")?;
+        if !self.parameters.is_empty() {
+            write!(f, ", {}", self.parameters)?;
         }
         writeln!(f, "): ...")?;
         Ok(())
@@ -186,30 +416,302 @@ impl fmt::Display for NewDef {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct ClassDef {
+struct ClassAttrDef {
     name: &'static str,
-    doc: &'static str,
+    r#type: TypeInfo,
+}
+
+impl ClassAttrDef {
+    fn from_info(info: &MemberInfo) -> Self {
+        Self {
+            name: info.name,
+            r#type: (info.r#type)(),
+        }
+    }
+
+    /// Names in `UPPER_SNAKE_CASE` are regarded as constants following PEP 8
+    fn is_constant(&self) -> bool {
+        self.name.chars().any(|c| c.is_ascii_uppercase())
+            && !self.name.chars().any(|c| c.is_ascii_lowercase())
+    }
+}
+
+impl Import for ClassAttrDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = self.r#type.import.clone();
+        if self.is_constant() {
+            import.insert(ImportRef::name("typing", "Final"));
+        } else {
+            import.insert(ImportRef::name("typing", "ClassVar"));
+        }
+        import
+    }
+}
+
+impl fmt::Display for ClassAttrDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = indent();
+        if self.is_constant() {
+            writeln!(f, "{indent}{}: Final[{}]", self.name, self.r#type)
+        } else {
+            writeln!(f, "{indent}{}: ClassVar[{}]", self.name, self.r#type)
+        }
+    }
+}
+
+/// Members and methods of classes and enums, defined by `#[pyo3(get, set)]` and `#[pymethods]`
+#[derive(Debug, Clone, PartialEq, Default)]
+struct ClassBody {
+    /// Whether setters are ignored since the class is `#[pyclass(frozen)]`
+    frozen: bool,
+    class_attrs: Vec<ClassAttrDef>,
     new: Option<NewDef>,
     members: Vec<MemberDef>,
     methods: Vec<MethodDef>,
 }
 
+impl ClassBody {
+    /// Merge a `#[pymethods]` block into the body
+    ///
+    /// A class may have multiple blocks with the `multiple-pymethods` feature of PyO3.
+    /// Definitions repeated identically, e.g. by macro-generated blocks, are merged into one,
+    /// while different definitions of the same name are reported as an error.
+    fn add_methods(&mut self, info: &PyMethodsInfo) -> Result<()> {
+        let struct_name = (info.struct_name)();
+        self.add_members(struct_name, info.getters, info.setters)?;
+        for class_attr in info.class_attrs {
+            let class_attr = ClassAttrDef::from_info(class_attr);
+            match self.class_attrs.iter().find(|c| c.name == class_attr.name) {
+                Some(defined) if defined == &class_attr => {}
+                Some(_) => bail!(
+                    "Class attribute `{}` of `{struct_name}` is defined differently in multiple #[gen_stub_pymethods]",
+                    class_attr.name
+                ),
+                None => self.class_attrs.push(class_attr),
+            }
+        }
+        for method in info.methods {
+            let method = MethodDef::from_info(method);
+            match self.methods.iter().find(|m| m.name == method.name) {
+                Some(defined) if defined == &method => {}
+                Some(_) => bail!(
+                    "Method `{}` of `{struct_name}` is defined differently in multiple #[gen_stub_pymethods]",
+                    method.name
+                ),
+                None => self.methods.push(method),
+            }
+        }
+        if let Some(new) = &info.new {
+            let new = NewDef::from_info(new);
+            match &self.new {
+                Some(defined) if defined == &new => {}
+                Some(_) => bail!(
+                    "Multiple #[new] are defined for `{struct_name}` in #[gen_stub_pymethods]"
+                ),
+                None => self.new = Some(new),
+            }
+        }
+        self.check_names(struct_name)
+    }
+
+    /// Pair getters and setters by name, merging into members already defined
+    ///
+    /// Getters or setters of the same name are merged if they are identical as [Self::add_methods].
+    fn add_members(
+        &mut self,
+        struct_name: &str,
+        getters: &[MemberInfo],
+        setters: &[MemberInfo],
+    ) -> Result<()> {
+        for info in getters {
+            let r#type = (info.r#type)();
+            let member = self.member_mut(info.name);
+            match &member.getter {
+                Some(defined) if defined == &r#type => {}
+                Some(_) => bail!(
+                    "Getter `{}` of `{struct_name}` is defined differently in multiple places",
+                    info.name
+                ),
+                None => member.getter = Some(r#type),
+            }
+        }
+        if self.frozen {
+            return Ok(());
+        }
+        for info in setters {
+            let r#type = (info.r#type)();
+            let member = self.member_mut(info.name);
+            match &member.setter {
+                Some(defined) if defined == &r#type => {}
+                Some(_) => bail!(
+                    "Setter `{}` of `{struct_name}` is defined differently in multiple places",
+                    info.name
+                ),
+                None => member.setter = Some(r#type),
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that class attributes, members and methods do not share a name
+    fn check_names(&self, struct_name: &str) -> Result<()> {
+        let names = self
+            .class_attrs
+            .iter()
+            .map(|c| (c.name, "class attribute"))
+            .chain(self.members.iter().map(|m| (m.name, "member")))
+            .chain(self.methods.iter().map(|m| (m.name, "method")));
+        let mut defined = BTreeMap::new();
+        for (name, kind) in names {
+            if let Some(other) = defined.insert(name, kind) {
+                bail!("`{name}` of `{struct_name}` is defined as both {other} and {kind}");
+            }
+        }
+        Ok(())
+    }
+
+    fn member_mut(&mut self, name: &'static str) -> &mut MemberDef {
+        if let Some(index) = self.members.iter().position(|member| member.name == name) {
+            return &mut self.members[index];
+        }
+        self.members.push(MemberDef {
+            name,
+            getter: None,
+            setter: None,
+        });
+        self.members.last_mut().unwrap()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.class_attrs.is_empty()
+            && self.members.is_empty()
+            && self.new.is_none()
+            && self.methods.is_empty()
+    }
+}
+
+impl Import for ClassBody {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = BTreeSet::new();
+        for class_attr in &self.class_attrs {
+            import.extend(class_attr.import());
+        }
+        for member in &self.members {
+            import.extend(member.import());
+        }
+        if let Some(new) = &self.new {
+            import.extend(new.import());
+        }
+        for method in &self.methods {
+            import.extend(method.import());
+        }
+        import
+    }
+}
+
+impl fmt::Display for ClassBody {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for class_attr in &self.class_attrs {
+            class_attr.fmt(f)?;
+        }
+        for member in &self.members {
+            member.fmt(f)?;
+        }
+        if let Some(new) = &self.new {
+            new.fmt(f)?;
+        }
+        for method in &self.methods {
+            method.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ClassDef {
+    name: &'static str,
+    doc: &'static str,
+    base: Option<TypeInfo>,
+    subclass: bool,
+    body: ClassBody,
+}
+
 impl ClassDef {
-    fn from_info(info: &PyClassInfo) -> Self {
-        Self {
+    fn from_info(info: &PyClassInfo) -> Result<Self> {
+        let mut body = ClassBody {
+            frozen: info.frozen,
+            ..Default::default()
+        };
+        body.add_members(info.pyclass_name, info.members, info.setters)?;
+        body.methods = Self::synthesized_methods(info);
+        Ok(Self {
             name: info.pyclass_name,
-            new: None,
             doc: info.doc,
-            members: info.members.iter().map(MemberDef::from_info).collect(),
-            methods: Vec::new(),
+            base: info.base.map(|base| base()),
+            subclass: info.subclass,
+            body,
+        })
+    }
+}
+
+impl ClassDef {
+    /// Methods generated by `#[pyclass(eq, ord, hash, str)]`
+    fn synthesized_methods(info: &PyClassInfo) -> Vec<MethodDef> {
+        let bool_ = TypeInfo::builtin("bool");
+        let mut methods = Vec::new();
+        if info.eq {
+            for name in ["__eq__", "__ne__"] {
+                let other = ("other", TypeInfo::builtin("object"));
+                methods.push(MethodDef::synthesized(name, vec![other], bool_.clone()));
+            }
+        }
+        if info.ord {
+            for name in ["__lt__", "__le__", "__gt__", "__ge__"] {
+                let other = ("other", TypeInfo::builtin(info.pyclass_name));
+                methods.push(MethodDef::synthesized(name, vec![other], bool_.clone()));
+            }
+        }
+        if info.hash {
+            methods.push(MethodDef::synthesized(
+                "__hash__",
+                Vec::new(),
+                TypeInfo::builtin("int"),
+            ));
+        }
+        if info.str {
+            methods.push(MethodDef::synthesized(
+                "__str__",
+                Vec::new(),
+                TypeInfo::builtin("str"),
+            ));
+        }
+        methods
+    }
+}
+
+impl Import for ClassDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = self.body.import();
+        if !self.subclass {
+            import.insert(ImportRef::name("typing", "final"));
+        }
+        if let Some(base) = &self.base {
+            import.extend(base.import.iter().cloned());
         }
+        import
     }
 }
 
 impl fmt::Display for ClassDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "@final")?;
-        writeln!(f, "class {}:", self.name)?;
+        if !self.subclass {
+            writeln!(f, "@final")?;
+        }
+        if let Some(base) = &self.base {
+            writeln!(f, "class {}({}):", self.name, base)?;
+        } else {
+            writeln!(f, "class {}:", self.name)?;
+        }
         let indent = indent();
         let doc = self.doc.trim();
         if !doc.is_empty() {
@@ -219,16 +721,8 @@ impl fmt::Display for ClassDef {
             }
             writeln!(f, r#"{indent}""""#)?;
         }
-        for member in &self.members {
-            member.fmt(f)?;
-        }
-        if let Some(new) = &self.new {
-            new.fmt(f)?;
-        }
-        for method in &self.methods {
-            method.fmt(f)?;
-        }
-        if self.members.is_empty() && self.methods.is_empty() {
+        self.body.fmt(f)?;
+        if self.body.is_empty() {
             writeln!(f, "{indent}...")?;
         }
         writeln!(f)?;
@@ -240,7 +734,8 @@ impl fmt::Display for ClassDef {
 struct EnumDef {
     name: &'static str,
     doc: &'static str,
-    variants: &'static [&'static str],
+    variants: Vec<VariantDef>,
+    body: ClassBody,
 }
 
 impl EnumDef {
@@ -248,15 +743,50 @@ impl EnumDef {
         Self {
             name: info.pyclass_name,
             doc: info.doc,
-            variants: info.variants,
+            variants: info
+                .variants
+                .iter()
+                .map(|variant| VariantDef::from_info(variant, info.pyclass_name))
+                .collect(),
+            body: ClassBody::default(),
+        }
+    }
+
+    /// Enum with tuple or struct variants, which is not a Python `Enum`
+    fn is_complex(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| variant.kind != VariantKind::Unit)
+    }
+}
+
+impl Import for EnumDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = self.body.import();
+        import.insert(ImportRef::name("typing", "final"));
+        if self.is_complex() {
+            for variant in &self.variants {
+                import.extend(variant.import());
+            }
+        } else {
+            import.extend([
+                ImportRef::name("enum", "Enum"),
+                ImportRef::name("enum", "auto"),
+            ]);
         }
+        import
     }
 }
 
 impl fmt::Display for EnumDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "@final")?;
-        writeln!(f, "class {}(Enum):", self.name)?;
+        let is_complex = self.is_complex();
+        if is_complex {
+            writeln!(f, "class {}:", self.name)?;
+        } else {
+            writeln!(f, "@final")?;
+            writeln!(f, "class {}(Enum):", self.name)?;
+        }
         let indent = indent();
         let doc = self.doc.trim();
         if !doc.is_empty() {
@@ -266,49 +796,158 @@ impl fmt::Display for EnumDef {
             }
             writeln!(f, r#"{indent}""""#)?;
         }
-        for variants in self.variants {
-            writeln!(f, "{indent}{} = auto()", variants)?;
+        for variant in &self.variants {
+            if is_complex {
+                variant.fmt(f)?;
+            } else {
+                writeln!(f, "{indent}{} = auto()", variant.name)?;
+            }
         }
+        self.body.fmt(f)?;
         writeln!(f)?;
         Ok(())
     }
 }
 
+/// Variant of enum, rendered as a nested class if the enum is complex
+#[derive(Debug, Clone, PartialEq)]
+struct VariantDef {
+    name: &'static str,
+    /// Name of the enum, which is the base class of the variant class
+    base: &'static str,
+    kind: VariantKind,
+    fields: Vec<(&'static str, TypeInfo)>,
+    /// Arguments of `__new__`
+    args: Vec<(&'static str, TypeInfo)>,
+}
+
+impl VariantDef {
+    fn from_info(info: &VariantInfo, base: &'static str) -> Self {
+        Self {
+            name: info.name,
+            base,
+            kind: info.kind,
+            fields: info
+                .fields
+                .iter()
+                .map(|field| (field.name, (field.r#type)()))
+                .collect(),
+            args: info
+                .args
+                .iter()
+                .map(|arg| (arg.name, (arg.r#type)()))
+                .collect(),
+        }
+    }
+}
+
+impl Import for VariantDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        self.fields
+            .iter()
+            .chain(&self.args)
+            .flat_map(|(_, r#type)| r#type.import.iter().cloned())
+            .collect()
+    }
+}
+
+impl fmt::Display for VariantDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = indent();
+        writeln!(f, "{indent}@final")?;
+        writeln!(f, "{indent}class {}({}):", self.name, self.base)?;
+        let match_args = self
+            .fields
+            .iter()
+            .map(|(name, _)| format!("\"{name}\""))
+            .collect::<Vec<_>>();
+        match match_args.as_slice() {
+            [arg] => writeln!(f, "{indent}{indent}__match_args__ = ({arg},)")?,
+            args => writeln!(f, "{indent}{indent}__match_args__ = ({})", args.join(", "))?,
+        }
+        for (name, ty) in &self.fields {
+            writeln!(f, "{indent}{indent}@property")?;
+            writeln!(f, "{indent}{indent}def {name}(self) -> {ty}: ...")?;
+        }
+        let params = self
+            .args
+            .iter()
+            .map(|(name, ty)| format!(", {name}: {ty}"))
+            .join("");
+        writeln!(f, "{indent}{indent}def __new__(cls{params}): ...")?;
+        Ok(())
+    }
+}
+
+/// Signature given by `#[gen_stub(overload = "...")]` without trailing `:` if exists
+///
+/// Only these signatures are rendered in place of the signature of the Rust function,
+/// and the docstring is placed on the last one.
+fn overload_signature(overload: &OverloadInfo) -> &str {
+    let overload = overload.signature.trim();
+    overload.strip_suffix(':').unwrap_or(overload).trim_end()
+}
+
+/// `typing.overload` and modules declared by `imports` of overloads
+fn overload_import(overloads: &[OverloadInfo]) -> BTreeSet<ImportRef> {
+    let mut import: BTreeSet<_> = overloads
+        .iter()
+        .flat_map(|overload| overload.imports)
+        .map(|module| ImportRef::Module(module.to_string()))
+        .collect();
+    import.insert(ImportRef::name("typing", "overload"));
+    import
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct FunctionDef {
     name: &'static str,
-    args: Vec<Arg>,
+    parameters: Parameters,
     r#return: ReturnTypeInfo,
-    signature: Option<&'static str>,
     doc: &'static str,
+    is_async: bool,
+    overloads: &'static [OverloadInfo],
 }
 
 impl FunctionDef {
     fn from_info(info: &PyFunctionInfo) -> Self {
         Self {
             name: info.name,
-            args: info.args.iter().map(Arg::from_info).collect(),
+            parameters: Parameters::new(info.args, info.signature),
             r#return: (info.r#return)().into(),
             doc: info.doc,
-            signature: info.signature,
+            is_async: info.is_async,
+            overloads: info.overloads,
+        }
+    }
+}
+
+impl Import for FunctionDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        // Rust types are not rendered if overloads are given
+        if !self.overloads.is_empty() {
+            return overload_import(self.overloads);
         }
+        let mut import = self.r#return.import();
+        import.extend(self.parameters.import());
+        import
     }
 }
 
 impl fmt::Display for FunctionDef {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "def {}(", self.name)?;
-        if let Some(sig) = self.signature {
-            write!(f, "{}", sig)?;
-        } else {
-            for (i, arg) in self.args.iter().enumerate() {
-                write!(f, "{}", arg)?;
-                if i != self.args.len() - 1 {
-                    write!(f, ",")?;
-                }
+        if let Some((last, overloads)) = self.overloads.split_last() {
+            for overload in overloads {
+                writeln!(f, "@overload")?;
+                writeln!(f, "{}: ...", overload_signature(overload))?;
             }
+            writeln!(f, "@overload")?;
+            writeln!(f, "{}:", overload_signature(last))?;
+        } else {
+            let r#async = if self.is_async { "async " } else { "" };
+            write!(f, "{async}def {}({}", self.name, self.parameters)?;
+            writeln!(f, "){}:", self.r#return)?;
         }
-        writeln!(f, "){}:", self.r#return)?;
 
         let doc = self.doc;
         let indent = indent();
@@ -325,23 +964,200 @@ impl fmt::Display for FunctionDef {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+struct ErrorDef {
+    name: &'static str,
+    base: TypeInfo,
+    doc: &'static str,
+}
+
+impl ErrorDef {
+    fn from_info(info: &PyErrorInfo) -> Self {
+        Self {
+            name: info.name,
+            base: (info.base)(),
+            doc: info.doc,
+        }
+    }
+}
+
+impl Import for ErrorDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        self.base.import.clone()
+    }
+}
+
+impl fmt::Display for ErrorDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "class {}({}):", self.name, self.base)?;
+        let indent = indent();
+        let doc = self.doc.trim();
+        if !doc.is_empty() {
+            writeln!(f, r#"{indent}r""""#)?;
+            for line in doc.lines() {
+                writeln!(f, "{indent}{}", line)?;
+            }
+            writeln!(f, r#"{indent}""""#)?;
+        }
+        writeln!(f, "{indent}...")?;
+        writeln!(f)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct VariableDef {
+    name: &'static str,
+    r#type: TypeInfo,
+    is_final: bool,
+}
+
+impl VariableDef {
+    fn from_info(info: &PyVariableInfo) -> Self {
+        Self {
+            name: info.name,
+            r#type: (info.r#type)(),
+            is_final: info.is_final,
+        }
+    }
+}
+
+impl Import for VariableDef {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = self.r#type.import.clone();
+        if self.is_final {
+            import.insert(ImportRef::name("typing", "Final"));
+        }
+        import
+    }
+}
+
+impl fmt::Display for VariableDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_final {
+            writeln!(f, "{}: Final[{}]", self.name, self.r#type)
+        } else {
+            writeln!(f, "{}: {}", self.name, self.r#type)
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Module {
     class: BTreeMap<TypeId, ClassDef>,
     enum_: BTreeMap<TypeId, EnumDef>,
     function: BTreeMap<&'static str, FunctionDef>,
-    error: BTreeSet<&'static str>,
+    error: BTreeMap<&'static str, ErrorDef>,
+    variable: BTreeMap<&'static str, VariableDef>,
+    /// Full name of this module, e.g. `my_module.sub`
+    name: String,
+    /// Name of the default module to resolve [ModuleRef::Default]
+    default_module_name: String,
+}
+
+impl Module {
+    /// Classes sorted by name, where base classes defined in this module precede their subclasses
+    fn sorted_classes(&self) -> Vec<&ClassDef> {
+        fn visit<'a>(
+            class: &'a ClassDef,
+            classes: &BTreeMap<&str, &'a ClassDef>,
+            visited: &mut BTreeSet<&'a str>,
+            sorted: &mut Vec<&'a ClassDef>,
+        ) {
+            if !visited.insert(class.name) {
+                return;
+            }
+            if let Some(base) = class
+                .base
+                .as_ref()
+                .and_then(|base| classes.get(&*base.name))
+            {
+                visit(base, classes, visited, sorted);
+            }
+            sorted.push(class);
+        }
+
+        let classes: BTreeMap<&str, &ClassDef> = self
+            .class
+            .values()
+            .map(|class| (class.name, class))
+            .collect();
+        let mut visited = BTreeSet::new();
+        let mut sorted = Vec::new();
+        for class in classes.values() {
+            visit(class, &classes, &mut visited, &mut sorted);
+        }
+        sorted
+    }
+
+    fn resolve<'a>(&'a self, module: &'a ModuleRef) -> &'a str {
+        match module {
+            ModuleRef::Named(name) => name,
+            ModuleRef::Default => &self.default_module_name,
+        }
+    }
+}
+
+impl Import for Module {
+    fn import(&self) -> BTreeSet<ImportRef> {
+        let mut import = BTreeSet::new();
+        for class in self.class.values() {
+            import.extend(class.import());
+        }
+        for enum_ in self.enum_.values() {
+            import.extend(enum_.import());
+        }
+        for function in self.function.values() {
+            import.extend(function.import());
+        }
+        for error in self.error.values() {
+            import.extend(error.import());
+        }
+        for variable in self.variable.values() {
+            import.extend(variable.import());
+        }
+        import
+    }
 }
 
 impl fmt::Display for Module {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "# This file is automatically generated by pyo3_stub_gen")?;
         writeln!(f)?;
-        writeln!(f, "from typing import final, Any, List, Dict")?;
-        writeln!(f, "from enum import Enum, auto")?;
-        writeln!(f)?;
 
-        for class in self.class.values().sorted_by_key(|class| class.name) {
+        // `import {module}` comes first, and then `from {module} import {names}` as isort does
+        let import = self.import();
+        let mut lines = Vec::new();
+        for import in &import {
+            if let ImportRef::Module(module) = import {
+                lines.push(format!("import {}", module));
+            }
+        }
+        let from_import = import
+            .iter()
+            .filter_map(|import| match import {
+                ImportRef::Name { module, name } => Some((self.resolve(module), name)),
+                ImportRef::Module(_) => None,
+            })
+            // Types defined in this module do not need to be imported
+            .filter(|(module, _)| *module != self.name)
+            .into_group_map();
+        for (module, names) in from_import.into_iter().sorted() {
+            let names = names.into_iter().sorted().dedup().join(", ");
+            lines.push(format!("from {} import {}", module, names));
+        }
+        if !lines.is_empty() {
+            writeln!(f, "{}", lines.join("\n"))?;
+            writeln!(f)?;
+        }
+
+        for variable in self.variable.values() {
+            write!(f, "{}", variable)?;
+        }
+        if !self.variable.is_empty() {
+            writeln!(f)?;
+        }
+        for class in self.sorted_classes() {
             write!(f, "{}", class)?;
         }
         for enum_ in self.enum_.values().sorted_by_key(|class| class.name) {
@@ -350,8 +1166,8 @@ impl fmt::Display for Module {
         for function in self.function.values() {
             write!(f, "{}", function)?;
         }
-        for error in self.error.iter() {
-            writeln!(f, "class {}(Exception): ...", error)?;
+        for error in self.error.values() {
+            write!(f, "{}", error)?;
         }
         Ok(())
     }
@@ -366,7 +1182,7 @@ pub struct StubInfo {
 impl StubInfo {
     pub fn from_pyproject_toml(path: impl AsRef<Path>) -> Result<Self> {
         let pyproject = PyProject::parse_toml(path)?;
-        Ok(Self::gather(pyproject))
+        Self::gather(pyproject)
     }
 
     fn default_module(&self) -> Result<&Module> {
@@ -376,7 +1192,7 @@ impl StubInfo {
             .ok_or_else(|| anyhow!("Missing default module: {}", default_module_name))
     }
 
-    fn gather(pyproject: PyProject) -> Self {
+    fn gather(pyproject: PyProject) -> Result<Self> {
         let default_module_name = pyproject.module_name();
         let mut modules: BTreeMap<String, Module> = BTreeMap::new();
 
@@ -389,7 +1205,7 @@ impl StubInfo {
 
             module
                 .class
-                .insert((info.struct_id)(), ClassDef::from_info(info));
+                .insert((info.struct_id)(), ClassDef::from_info(info)?);
         }
 
         for info in inventory::iter::<PyEnumInfo> {
@@ -403,26 +1219,30 @@ impl StubInfo {
                 .insert((info.enum_id)(), EnumDef::from_info(info));
         }
 
-        'methods_info: for info in inventory::iter::<PyMethodsInfo> {
+        // Sort blocks by their location in the source code,
+        // since the iteration order of inventory is not specified
+        let mut methods_infos: Vec<&PyMethodsInfo> =
+            inventory::iter::<PyMethodsInfo>.into_iter().collect();
+        methods_infos.sort_by_key(|info| (info.file, info.line));
+        for info in methods_infos {
             let struct_id = (info.struct_id)();
-            for module in modules.values_mut() {
-                if let Some(entry) = module.class.get_mut(&struct_id) {
-                    for getter in info.getters {
-                        entry.members.push(MemberDef {
-                            name: getter.name,
-                            r#type: (getter.r#type)(),
-                        });
-                    }
-                    for method in info.methods {
-                        entry.methods.push(MethodDef::from_info(method))
-                    }
-                    if let Some(new) = &info.new {
-                        entry.new = Some(NewDef::from_info(new));
-                    }
-                    continue 'methods_info;
+            let body = modules.values_mut().find_map(|module| {
+                if let Some(class) = module.class.get_mut(&struct_id) {
+                    Some(&mut class.body)
+                } else {
+                    module
+                        .enum_
+                        .get_mut(&struct_id)
+                        .map(|enum_| &mut enum_.body)
                 }
-            }
-            unreachable!("Missing struct_id = {:?}", struct_id);
+            });
+            let Some(body) = body else {
+                bail!(
+                    "Missing #[gen_stub_pyclass] or #[gen_stub_pyclass_enum] for `{}` used in #[gen_stub_pymethods]",
+                    (info.struct_name)()
+                );
+            };
+            body.add_methods(info)?;
         }
 
         for info in inventory::iter::<PyFunctionInfo> {
@@ -438,12 +1258,27 @@ impl StubInfo {
                 .insert(info.name, FunctionDef::from_info(info));
         }
 
-        let default = modules.entry(default_module_name.to_string()).or_default();
         for info in inventory::iter::<PyErrorInfo> {
-            default.error.insert(info.name);
+            let module = modules.entry(info.module.to_string()).or_default();
+            module.error.insert(info.name, ErrorDef::from_info(info));
+        }
+
+        for info in inventory::iter::<PyVariableInfo> {
+            let module = modules.entry(info.module.to_string()).or_default();
+            module
+                .variable
+                .insert(info.name, VariableDef::from_info(info));
+        }
+
+        // The default module is always generated even if it is empty
+        modules.entry(default_module_name.to_string()).or_default();
+
+        for (name, module) in modules.iter_mut() {
+            module.name = name.clone();
+            module.default_module_name = default_module_name.to_string();
         }
 
-        Self { modules, pyproject }
+        Ok(Self { modules, pyproject })
     }
 
     pub fn generate(&self) -> Result<()> {
@@ -476,3 +1311,516 @@ impl StubInfo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn int() -> TypeInfo {
+        TypeInfo::builtin("int")
+    }
+
+    fn str_() -> TypeInfo {
+        TypeInfo::builtin("str")
+    }
+
+    fn float() -> TypeInfo {
+        TypeInfo::builtin("float")
+    }
+
+    const fn arg(name: &'static str, r#type: fn() -> TypeInfo) -> ArgInfo {
+        ArgInfo {
+            name,
+            r#type,
+            is_optional: false,
+        }
+    }
+
+    const fn member(name: &'static str, r#type: fn() -> TypeInfo) -> MemberInfo {
+        MemberInfo { name, r#type }
+    }
+
+    /// Instance method without any options
+    const fn method(
+        name: &'static str,
+        args: &'static [ArgInfo],
+        r#return: fn() -> TypeInfo,
+    ) -> MethodInfo {
+        MethodInfo {
+            name,
+            args,
+            r#return,
+            signature: None,
+            doc: "",
+            is_static: false,
+            is_class: false,
+            is_async: false,
+            overloads: &[],
+            is_return_overridden: false,
+        }
+    }
+
+    /// Function without any options
+    const fn function(
+        name: &'static str,
+        args: &'static [ArgInfo],
+        r#return: fn() -> TypeInfo,
+    ) -> PyFunctionInfo {
+        PyFunctionInfo {
+            name,
+            args,
+            r#return,
+            doc: "",
+            signature: None,
+            module: None,
+            is_async: false,
+            overloads: &[],
+        }
+    }
+
+    /// Empty `#[pymethods]` block of class `A`
+    const fn methods_block() -> PyMethodsInfo {
+        PyMethodsInfo {
+            struct_id: std::any::TypeId::of::<()>,
+            struct_name: || "A",
+            new: None,
+            getters: &[],
+            setters: &[],
+            class_attrs: &[],
+            methods: &[],
+            file: "",
+            line: 0,
+        }
+    }
+
+    #[test]
+    fn test_module_import() {
+        let mut module = Module::default();
+        module.function.insert(
+            "f",
+            FunctionDef {
+                name: "f",
+                parameters: Parameters(vec![Parameter::Arg(Arg {
+                    name: "x",
+                    r#type: TypeInfo::imported("collections.abc", "Sequence")
+                        .with_args([TypeInfo::any()]),
+                    default: None,
+                })]),
+                r#return: TypeInfo {
+                    name: "datetime.date".to_string(),
+                    import: [ImportRef::Module("datetime".to_string())].into(),
+                }
+                .into(),
+                doc: "",
+                is_async: false,
+                overloads: &[],
+            },
+        );
+        assert_eq!(
+            module.to_string(),
+            r#"# This file is automatically generated by pyo3_stub_gen
+
+import datetime
+from collections.abc import Sequence
+from typing import Any
+
+def f(x: Sequence[Any]) -> datetime.date:
+    ...
+
+"#
+        );
+    }
+
+    #[test]
+    fn test_module_import_class() {
+        let mut module = Module {
+            name: "pkg".to_string(),
+            default_module_name: "pkg".to_string(),
+            ..Default::default()
+        };
+        module.function.insert(
+            "f",
+            FunctionDef {
+                name: "f",
+                parameters: Parameters(vec![Parameter::Arg(Arg {
+                    name: "a",
+                    r#type: TypeInfo::locally_defined("A", "pkg.sub".into()),
+                    default: None,
+                })]),
+                r#return: TypeInfo::locally_defined("B", ModuleRef::Default).into(),
+                doc: "",
+                is_async: false,
+                overloads: &[],
+            },
+        );
+        assert_eq!(
+            module.to_string(),
+            r#"# This file is automatically generated by pyo3_stub_gen
+
+from pkg.sub import A
+
+def f(a: A) -> B:
+    ...
+
+"#
+        );
+    }
+
+    #[test]
+    fn test_parameters_with_signature() {
+        let args = [
+            arg("a", int),
+            arg("b", str_),
+            arg("args", TypeInfo::any),
+            ArgInfo {
+                is_optional: true,
+                ..arg("c", || int() | TypeInfo::none())
+            },
+        ];
+        let signature = [
+            SignatureArg::Ident("a"),
+            SignatureArg::Slash,
+            SignatureArg::Assign("b", || TypeInfo::builtin("\"x\"")),
+            SignatureArg::Args("args"),
+            SignatureArg::Ident("c"),
+        ];
+        assert_eq!(
+            Parameters::new(&args, Some(&signature)).to_string(),
+            "a: int, /, b: str = \"x\", *args: Any, c: int | None"
+        );
+        assert_eq!(
+            Parameters::new(&args, None).to_string(),
+            "a: int, b: str, args: Any, c: int | None = None"
+        );
+    }
+
+    #[test]
+    fn test_class_members() {
+        let mut class = ClassDef {
+            name: "A",
+            doc: "",
+            base: None,
+            subclass: false,
+            body: ClassBody::default(),
+        };
+        class
+            .body
+            .add_members("A", &[member("x", int)], &[])
+            .unwrap();
+        class
+            .body
+            .add_members("A", &[member("y", int)], &[member("x", int)])
+            .unwrap();
+        assert_eq!(
+            class.to_string(),
+            r#"@final
+class A:
+    @property
+    def x(self) -> int: ...
+    @x.setter
+    def x(self, value: int) -> None: ...
+    @property
+    def y(self) -> int: ...
+
+"#
+        );
+    }
+
+    #[test]
+    fn test_class_options() {
+        const MEMBERS: &[MemberInfo] = &[member("x", int)];
+        let info = PyClassInfo {
+            struct_id: std::any::TypeId::of::<()>,
+            pyclass_name: "A",
+            module: None,
+            doc: "",
+            members: MEMBERS,
+            setters: MEMBERS,
+            base: None,
+            subclass: false,
+            eq: true,
+            ord: true,
+            hash: true,
+            str: true,
+            frozen: true,
+        };
+        assert_eq!(
+            ClassDef::from_info(&info).unwrap().to_string(),
+            r#"@final
+class A:
+    @property
+    def x(self) -> int: ...
+    def __eq__(self, other: object) -> bool:
+        ...
+
+    def __ne__(self, other: object) -> bool:
+        ...
+
+    def __lt__(self, other: A) -> bool:
+        ...
+
+    def __le__(self, other: A) -> bool:
+        ...
+
+    def __gt__(self, other: A) -> bool:
+        ...
+
+    def __ge__(self, other: A) -> bool:
+        ...
+
+    def __hash__(self) -> int:
+        ...
+
+    def __str__(self) -> str:
+        ...
+
+
+"#
+        );
+    }
+
+    #[test]
+    fn test_protocol_methods() {
+        fn this() -> TypeInfo {
+            TypeInfo::builtin("A")
+        }
+        let render = |name, args, is_return_overridden| {
+            MethodDef::from_info(&MethodInfo {
+                is_return_overridden,
+                ..method(name, args, this)
+            })
+            .to_string()
+        };
+        const OTHER: &[ArgInfo] = &[arg("other", int)];
+        assert_eq!(
+            render("__len__", &[], false),
+            "    def __len__(self) -> int:\n        ...\n\n"
+        );
+        assert_eq!(
+            render("__len__", &[], true),
+            "    def __len__(self) -> A:\n        ...\n\n"
+        );
+        assert_eq!(
+            render("__iter__", &[], false),
+            "    def __iter__(self) -> A:\n        ...\n\n"
+        );
+        assert_eq!(
+            render("__add__", OTHER, false),
+            "    def __add__(self, other: int) -> A:\n        ...\n\n"
+        );
+        assert_eq!(
+            render("__radd__", OTHER, false),
+            "    def __radd__(self, other: object) -> A:\n        ...\n\n"
+        );
+    }
+
+    #[test]
+    fn test_async_function() {
+        const SECS: &[ArgInfo] = &[arg("secs", int)];
+        let info = PyFunctionInfo {
+            is_async: true,
+            ..function("sleep", SECS, int)
+        };
+        assert_eq!(
+            FunctionDef::from_info(&info).to_string(),
+            "async def sleep(secs: int) -> int:\n    ...\n\n"
+        );
+        fn awaitable() -> TypeInfo {
+            crate::type_info::awaitable_output::<usize>()
+        }
+        let info = PyFunctionInfo {
+            r#return: awaitable,
+            is_async: false,
+            ..info
+        };
+        let function = FunctionDef::from_info(&info);
+        assert_eq!(
+            function.to_string(),
+            "def sleep(secs: int) -> Awaitable[int]:\n    ...\n\n"
+        );
+        assert_eq!(
+            function.import(),
+            [ImportRef::name("collections.abc", "Awaitable")].into()
+        );
+    }
+
+    #[test]
+    fn test_overload() {
+        const X: &[ArgInfo] = &[arg("x", int)];
+        let info = PyFunctionInfo {
+            doc: "Doubles x",
+            overloads: &[
+                OverloadInfo {
+                    signature: "def double(x: bool) -> bool:",
+                    imports: &[],
+                },
+                OverloadInfo {
+                    signature: "def double(x: collections.abc.Sequence[int]) -> list[int]",
+                    imports: &["collections.abc"],
+                },
+            ],
+            ..function("double", X, int)
+        };
+        let function = FunctionDef::from_info(&info);
+        assert_eq!(
+            function.to_string(),
+            r#"@overload
+def double(x: bool) -> bool: ...
+@overload
+def double(x: collections.abc.Sequence[int]) -> list[int]:
+    r"""
+    Doubles x
+    """
+    ...
+
+"#
+        );
+        assert_eq!(
+            function.import(),
+            [
+                ImportRef::name("typing", "overload"),
+                ImportRef::Module("collections.abc".to_string())
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_merge_methods() {
+        const F: &[MethodInfo] = &[method("f", &[], int)];
+        const F_G: &[MethodInfo] = &[method("f", &[], int), method("g", &[], str_)];
+        const G_INT: &[MethodInfo] = &[method("g", &[], int)];
+        const X: &[ArgInfo] = &[arg("x", int)];
+        let block = |new, methods| PyMethodsInfo {
+            new,
+            methods,
+            ..methods_block()
+        };
+        let new = |args| NewInfo {
+            args,
+            signature: None,
+        };
+
+        let mut body = ClassBody::default();
+        body.add_methods(&block(Some(new(&[])), F)).unwrap();
+        // Same definition is merged
+        body.add_methods(&block(None, F_G)).unwrap();
+        assert_eq!(
+            body.methods.iter().map(|m| m.name).collect::<Vec<_>>(),
+            ["f", "g"]
+        );
+
+        let err = body.add_methods(&block(None, G_INT)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Method `g` of `A` is defined differently in multiple #[gen_stub_pymethods]"
+        );
+        // Same constructor is merged, but different one is not
+        body.add_methods(&block(Some(new(&[])), &[])).unwrap();
+        let err = body.add_methods(&block(Some(new(X)), &[])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Multiple #[new] are defined for `A` in #[gen_stub_pymethods]"
+        );
+    }
+
+    #[test]
+    fn test_merge_members() {
+        const X_INT: &[MemberInfo] = &[member("x", int)];
+        const X_STR: &[MemberInfo] = &[member("x", str_)];
+        let block = |getters, setters, class_attrs| PyMethodsInfo {
+            getters,
+            setters,
+            class_attrs,
+            ..methods_block()
+        };
+
+        let mut body = ClassBody::default();
+        body.add_methods(&block(X_INT, X_INT, &[])).unwrap();
+        // Same definition is merged
+        body.add_methods(&block(X_INT, X_INT, &[])).unwrap();
+        assert_eq!(body.members.len(), 1);
+
+        let err = body.add_methods(&block(X_STR, &[], &[])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Getter `x` of `A` is defined differently in multiple places"
+        );
+        let err = body.add_methods(&block(&[], X_STR, &[])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Setter `x` of `A` is defined differently in multiple places"
+        );
+        let err = body.add_methods(&block(&[], &[], X_INT)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`x` of `A` is defined as both class attribute and member"
+        );
+    }
+
+    #[test]
+    fn test_complex_enum() {
+        fn float_list() -> TypeInfo {
+            TypeInfo::builtin("list").with_args([float()])
+        }
+        fn float_sequence() -> TypeInfo {
+            TypeInfo::imported("collections.abc", "Sequence").with_args([float()])
+        }
+        const RADIUS: &[MemberInfo] = &[member("radius", float)];
+        const PAIR: &[MemberInfo] = &[member("_0", float), member("_1", float)];
+        const FLOAT_LIST: &[MemberInfo] = &[member("_0", float_list)];
+        const FLOAT_SEQUENCE: &[MemberInfo] = &[member("_0", float_sequence)];
+        let info = PyEnumInfo {
+            enum_id: std::any::TypeId::of::<()>,
+            pyclass_name: "Shape",
+            module: None,
+            doc: "",
+            variants: &[
+                VariantInfo {
+                    name: "Circle",
+                    kind: VariantKind::Struct,
+                    fields: RADIUS,
+                    args: RADIUS,
+                },
+                VariantInfo {
+                    name: "Rect",
+                    kind: VariantKind::Tuple,
+                    fields: PAIR,
+                    args: PAIR,
+                },
+                VariantInfo {
+                    name: "Polygon",
+                    kind: VariantKind::Tuple,
+                    fields: FLOAT_LIST,
+                    args: FLOAT_SEQUENCE,
+                },
+            ],
+        };
+        assert_eq!(
+            EnumDef::from_info(&info).to_string(),
+            r#"class Shape:
+    @final
+    class Circle(Shape):
+        __match_args__ = ("radius",)
+        @property
+        def radius(self) -> float: ...
+        def __new__(cls, radius: float): ...
+    @final
+    class Rect(Shape):
+        __match_args__ = ("_0", "_1")
+        @property
+        def _0(self) -> float: ...
+        @property
+        def _1(self) -> float: ...
+        def __new__(cls, _0: float, _1: float): ...
+    @final
+    class Polygon(Shape):
+        __match_args__ = ("_0",)
+        @property
+        def _0(self) -> list[float]: ...
+        def __new__(cls, _0: Sequence[float]): ...
+
+"#
+        );
+    }
+}
@@ -1,9 +1,16 @@
 pub use inventory;
 pub use pyo3_stub_gen_derive as derive; // re-export to use in generated code
 
+mod exception;
 mod generate;
 mod pyproject;
+mod stub_type;
 pub mod type_info;
+mod variable;
 
 pub type Result<T> = anyhow::Result<T>;
 pub use generate::StubInfo;
+pub use stub_type::{ImportRef, ModuleRef, PyStubType, TypeInfo};
+
+#[cfg(feature = "numpy")]
+pub use stub_type::NumPyScalar;
@@ -0,0 +1,250 @@
+//! Python type annotations for Rust types
+//!
+//! [PyStubType] maps a Rust type to the Python type annotation which appears in the stub file.
+//! This crate implements it for Rust standard types and types provided by PyO3,
+//! and `#[gen_stub_pyclass]` and `#[gen_stub_pyclass_enum]` implement it for user-defined classes.
+
+/// Implement [PyStubType] for a type which is rendered as a fixed Python type name
+macro_rules! impl_builtin {
+    ($ty:ty, $pytype:expr) => {
+        impl PyStubType for $ty {
+            fn type_output() -> TypeInfo {
+                TypeInfo::builtin($pytype)
+            }
+        }
+    };
+}
+
+/// Implement [PyStubType] for a type which is rendered as a type qualified by its module, e.g. `datetime.date`
+#[cfg(any(feature = "chrono", feature = "rust_decimal", feature = "time"))]
+macro_rules! impl_qualified {
+    ($ty:ty, $module:expr, $name:expr) => {
+        impl PyStubType for $ty {
+            fn type_output() -> TypeInfo {
+                TypeInfo::qualified($module, $name)
+            }
+        }
+    };
+}
+
+mod builtins;
+#[cfg(feature = "chrono")]
+mod chrono;
+mod collections;
+#[cfg(feature = "either")]
+mod either;
+#[cfg(feature = "hashbrown")]
+mod hashbrown;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+#[cfg(feature = "num-complex")]
+mod num_complex;
+#[cfg(feature = "numpy")]
+mod numpy;
+mod pyo3;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "numpy")]
+pub use self::numpy::NumPyScalar;
+
+use std::{collections::BTreeSet, fmt, ops};
+
+/// Python module where a type is defined
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ModuleRef {
+    /// Module specified by its full name, e.g. `typing` or `my_module.sub`
+    Named(String),
+    /// The default module of the project, which is only known when the stub file is generated.
+    ///
+    /// This is used for `#[pyclass]` without `module = "..."` argument.
+    Default,
+}
+
+impl From<&str> for ModuleRef {
+    fn from(module: &str) -> Self {
+        Self::Named(module.to_string())
+    }
+}
+
+/// Import statement required to use a type annotation in the stub file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ImportRef {
+    /// `import {module}`, used by qualified names like `datetime.date`
+    Module(String),
+    /// `from {module} import {name}`
+    Name { module: ModuleRef, name: String },
+}
+
+impl ImportRef {
+    /// Shorthand for [ImportRef::Name]
+    pub fn name(module: impl Into<ModuleRef>, name: &str) -> Self {
+        Self::Name {
+            module: module.into(),
+            name: name.to_string(),
+        }
+    }
+}
+
+/// Type information for creating Python stub files annotated by [PyStubType] trait.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeInfo {
+    /// The Python type name.
+    pub name: String,
+    /// Imports required to use this type in the stub file.
+    pub import: BTreeSet<ImportRef>,
+}
+
+impl fmt::Display for TypeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl TypeInfo {
+    /// A `None` type annotation.
+    pub fn none() -> Self {
+        Self::builtin("None")
+    }
+
+    /// A `typing.Any` type annotation.
+    pub fn any() -> Self {
+        Self::imported("typing", "Any")
+    }
+
+    /// A type annotation of a built-in type, e.g. `int` or `str`.
+    pub fn builtin(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            import: BTreeSet::new(),
+        }
+    }
+
+    /// A type annotation imported by `from {module} import {name}`, e.g. `collections.abc.Sequence`.
+    pub fn imported(module: &str, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            import: [ImportRef::name(module, name)].into(),
+        }
+    }
+
+    /// A type annotation qualified by its module, e.g. `datetime.date` imported by `import datetime`.
+    pub fn qualified(module: &str, name: &str) -> Self {
+        Self {
+            name: format!("{module}.{name}"),
+            import: [ImportRef::Module(module.to_string())].into(),
+        }
+    }
+
+    /// A type annotation of a type defined in the stub files, e.g. classes by `#[pyclass]`.
+    ///
+    /// It is imported by `from {module} import {name}` when it is used in another module.
+    pub fn locally_defined(name: &str, module: ModuleRef) -> Self {
+        Self {
+            name: name.to_string(),
+            import: [ImportRef::name(module, name)].into(),
+        }
+    }
+
+    /// Subscript a generic type with type arguments, e.g. `list` and `[int]` into `list[int]`.
+    pub fn with_args(self, args: impl IntoIterator<Item = TypeInfo>) -> Self {
+        let mut import = self.import;
+        let mut names = Vec::new();
+        for arg in args {
+            names.push(arg.name);
+            import.extend(arg.import);
+        }
+        Self {
+            name: format!("{}[{}]", self.name, names.join(", ")),
+            import,
+        }
+    }
+}
+
+/// Union of two types, e.g. `int | None`
+impl ops::BitOr for TypeInfo {
+    type Output = Self;
+    fn bitor(mut self, rhs: Self) -> Self {
+        self.import.extend(rhs.import);
+        Self {
+            name: format!("{} | {}", self.name, rhs.name),
+            import: self.import,
+        }
+    }
+}
+
+/// Annotate Rust types with Python type information.
+pub trait PyStubType {
+    /// The type to be used in the output signature, i.e. return type of the Python function or methods.
+    fn type_output() -> TypeInfo;
+
+    /// The type to be used in the input signature, i.e. the arguments of the Python function or methods.
+    ///
+    /// This defaults to the output type, but can be overridden for types that are not valid input types.
+    fn type_input() -> TypeInfo {
+        Self::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::pyo3::PyAny;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_type_output() {
+        assert_eq!(<usize as PyStubType>::type_output().name, "int");
+        assert_eq!(
+            <Option<String> as PyStubType>::type_output().name,
+            "str | None"
+        );
+        assert_eq!(
+            <HashMap<String, Vec<f64>> as PyStubType>::type_output().name,
+            "dict[str, list[float]]"
+        );
+        assert_eq!(
+            <(u32, &str, bool) as PyStubType>::type_output().name,
+            "tuple[int, str, bool]"
+        );
+        assert_eq!(<() as PyStubType>::type_output().name, "None");
+    }
+
+    #[test]
+    fn test_type_input() {
+        assert_eq!(
+            <Vec<Vec<u32>> as PyStubType>::type_input().name,
+            "Sequence[Sequence[int]]"
+        );
+        assert_eq!(
+            <HashMap<String, Vec<f64>> as PyStubType>::type_input().name,
+            "Mapping[str, Sequence[float]]"
+        );
+        assert_eq!(
+            <Option<std::collections::BTreeSet<i64>> as PyStubType>::type_input(),
+            TypeInfo {
+                name: "collections.abc.Set[int] | None".to_string(),
+                import: [ImportRef::Module("collections.abc".to_string())].into(),
+            }
+        );
+        assert_eq!(<[u8] as PyStubType>::type_input().name, "bytes");
+    }
+
+    #[test]
+    fn test_import() {
+        assert!(<Vec<usize> as PyStubType>::type_output().import.is_empty());
+        assert_eq!(
+            <Option<Vec<&PyAny>> as PyStubType>::type_output(),
+            TypeInfo {
+                name: "list[Any] | None".to_string(),
+                import: [ImportRef::name("typing", "Any")].into(),
+            }
+        );
+    }
+}
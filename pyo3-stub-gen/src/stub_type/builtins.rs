@@ -0,0 +1,60 @@
+//! Define PyStubType for built-in types based on <https://pyo3.rs/v0.20.3/conversions/tables#argument-types>
+
+use crate::stub_type::*;
+use std::{
+    borrow::Cow,
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+impl PyStubType for () {
+    fn type_output() -> TypeInfo {
+        TypeInfo::none()
+    }
+}
+
+impl_builtin!(bool, "bool");
+impl_builtin!(u8, "int");
+impl_builtin!(u16, "int");
+impl_builtin!(u32, "int");
+impl_builtin!(u64, "int");
+impl_builtin!(u128, "int");
+impl_builtin!(usize, "int");
+impl_builtin!(i8, "int");
+impl_builtin!(i16, "int");
+impl_builtin!(i32, "int");
+impl_builtin!(i64, "int");
+impl_builtin!(i128, "int");
+impl_builtin!(isize, "int");
+impl_builtin!(f32, "float");
+impl_builtin!(f64, "float");
+
+impl_builtin!(char, "str");
+impl_builtin!(str, "str");
+impl_builtin!(String, "str");
+impl_builtin!(Cow<'_, str>, "str");
+impl_builtin!(OsStr, "str");
+impl_builtin!(OsString, "str");
+impl_builtin!(Path, "str");
+impl_builtin!(PathBuf, "str");
+impl_builtin!([u8], "bytes");
+
+impl<T: PyStubType + ?Sized> PyStubType for &T {
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+}
+
+/// `PyResult<T>` and other `Result<T, E>` returned from Rust functions are rendered as `T`,
+/// since the error is raised as a Python exception.
+impl<T: PyStubType, E> PyStubType for Result<T, E> {
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+}
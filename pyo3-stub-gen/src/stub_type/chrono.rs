@@ -0,0 +1,45 @@
+//! Define PyStubType for date and time types of [chrono](https://docs.rs/chrono), enabled by `chrono` feature
+//!
+//! See <https://pyo3.rs/v0.20.3/conversions/tables#mappings-for-optional-features> for conversions by PyO3.
+
+use crate::stub_type::*;
+use ::chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+
+impl_qualified!(NaiveDate, "datetime", "date");
+impl_qualified!(NaiveTime, "datetime", "time");
+impl_qualified!(NaiveDateTime, "datetime", "datetime");
+impl_qualified!(TimeDelta, "datetime", "timedelta");
+impl_qualified!(FixedOffset, "datetime", "tzinfo");
+impl_qualified!(Utc, "datetime", "tzinfo");
+
+impl<Tz: TimeZone> PyStubType for DateTime<Tz> {
+    fn type_output() -> TypeInfo {
+        TypeInfo::qualified("datetime", "datetime")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_chrono() {
+        assert_eq!(
+            <NaiveDate as PyStubType>::type_output(),
+            TypeInfo {
+                name: "datetime.date".to_string(),
+                import: [ImportRef::Module("datetime".to_string())].into(),
+            }
+        );
+        assert_eq!(
+            <DateTime<Utc> as PyStubType>::type_input().name,
+            "datetime.datetime"
+        );
+        assert_eq!(
+            <::chrono::Duration as PyStubType>::type_output().name,
+            "datetime.timedelta"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::stub_type::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+impl<T: PyStubType> PyStubType for Option<T> {
+    fn type_input() -> TypeInfo {
+        T::type_input() | TypeInfo::none()
+    }
+    fn type_output() -> TypeInfo {
+        T::type_output() | TypeInfo::none()
+    }
+}
+
+/// PyO3 extracts `Vec<T>` from any sequence except `str`, while it always returns `list`.
+impl<T: PyStubType> PyStubType for Vec<T> {
+    fn type_input() -> TypeInfo {
+        TypeInfo::imported("collections.abc", "Sequence").with_args([T::type_input()])
+    }
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("list").with_args([T::type_output()])
+    }
+}
+
+impl<T: PyStubType, const N: usize> PyStubType for [T; N] {
+    fn type_input() -> TypeInfo {
+        Vec::<T>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        Vec::<T>::type_output()
+    }
+}
+
+/// Sets are accepted as `collections.abc.Set` to allow `frozenset`, and returned as `set`.
+///
+/// `collections.abc.Set` is qualified by the module to be distinguished from builtin `set`.
+impl<T: PyStubType, State> PyStubType for HashSet<T, State> {
+    fn type_input() -> TypeInfo {
+        TypeInfo::qualified("collections.abc", "Set").with_args([T::type_input()])
+    }
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("set").with_args([T::type_output()])
+    }
+}
+
+impl<T: PyStubType> PyStubType for BTreeSet<T> {
+    fn type_input() -> TypeInfo {
+        HashSet::<T>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        HashSet::<T>::type_output()
+    }
+}
+
+/// Maps are accepted as `Mapping`, which is covariant in the value type unlike `dict`.
+impl<Key: PyStubType, Value: PyStubType, State> PyStubType for HashMap<Key, Value, State> {
+    fn type_input() -> TypeInfo {
+        TypeInfo::imported("collections.abc", "Mapping")
+            .with_args([Key::type_input(), Value::type_input()])
+    }
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("dict").with_args([Key::type_output(), Value::type_output()])
+    }
+}
+
+impl<Key: PyStubType, Value: PyStubType> PyStubType for BTreeMap<Key, Value> {
+    fn type_input() -> TypeInfo {
+        HashMap::<Key, Value>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        HashMap::<Key, Value>::type_output()
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($T:ident),*) => {
+        impl<$($T: PyStubType),*> PyStubType for ($($T,)*) {
+            fn type_output() -> TypeInfo {
+                TypeInfo::builtin("tuple").with_args([$($T::type_output()),*])
+            }
+            fn type_input() -> TypeInfo {
+                TypeInfo::builtin("tuple").with_args([$($T::type_input()),*])
+            }
+        }
+    };
+}
+
+impl_tuple!(T1);
+impl_tuple!(T1, T2);
+impl_tuple!(T1, T2, T3);
+impl_tuple!(T1, T2, T3, T4);
+impl_tuple!(T1, T2, T3, T4, T5);
+impl_tuple!(T1, T2, T3, T4, T5, T6);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
@@ -0,0 +1,31 @@
+//! Define PyStubType for [either](https://docs.rs/either), enabled by `either` feature
+
+use crate::stub_type::*;
+use ::either::Either;
+
+/// PyO3 extracts `Either<L, R>` by trying `L` first and then `R`
+impl<L: PyStubType, R: PyStubType> PyStubType for Either<L, R> {
+    fn type_input() -> TypeInfo {
+        L::type_input() | R::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        L::type_output() | R::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_either() {
+        assert_eq!(
+            <Either<i32, Vec<String>> as PyStubType>::type_input().name,
+            "int | Sequence[str]"
+        );
+        assert_eq!(
+            <Either<i32, Vec<String>> as PyStubType>::type_output().name,
+            "int | list[str]"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Define PyStubType for [hashbrown](https://docs.rs/hashbrown), enabled by `hashbrown` feature
+
+use crate::stub_type::*;
+
+impl<Key: PyStubType, Value: PyStubType, State> PyStubType
+    for ::hashbrown::HashMap<Key, Value, State>
+{
+    fn type_input() -> TypeInfo {
+        std::collections::HashMap::<Key, Value>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        std::collections::HashMap::<Key, Value>::type_output()
+    }
+}
+
+impl<T: PyStubType, State> PyStubType for ::hashbrown::HashSet<T, State> {
+    fn type_input() -> TypeInfo {
+        std::collections::HashSet::<T>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        std::collections::HashSet::<T>::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hashbrown() {
+        assert_eq!(
+            <::hashbrown::HashMap<i64, f64> as PyStubType>::type_output().name,
+            "dict[int, float]"
+        );
+        assert_eq!(
+            <::hashbrown::HashSet<String> as PyStubType>::type_output().name,
+            "set[str]"
+        );
+    }
+}
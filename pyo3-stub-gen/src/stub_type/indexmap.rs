@@ -0,0 +1,40 @@
+//! Define PyStubType for [indexmap](https://docs.rs/indexmap), enabled by `indexmap` feature
+
+use crate::stub_type::*;
+use ::indexmap::{IndexMap, IndexSet};
+use std::collections::{HashMap, HashSet};
+
+impl<Key: PyStubType, Value: PyStubType, State> PyStubType for IndexMap<Key, Value, State> {
+    fn type_input() -> TypeInfo {
+        HashMap::<Key, Value>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        HashMap::<Key, Value>::type_output()
+    }
+}
+
+impl<T: PyStubType, State> PyStubType for IndexSet<T, State> {
+    fn type_input() -> TypeInfo {
+        HashSet::<T>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        HashSet::<T>::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_indexmap() {
+        assert_eq!(
+            <IndexMap<String, i32> as PyStubType>::type_input().name,
+            "Mapping[str, int]"
+        );
+        assert_eq!(
+            <IndexMap<String, i32> as PyStubType>::type_output().name,
+            "dict[str, int]"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Define PyStubType for arbitrary precision integers of [num-bigint](https://docs.rs/num-bigint), enabled by `num-bigint` feature
+
+use crate::stub_type::*;
+use ::num_bigint::{BigInt, BigUint};
+
+impl_builtin!(BigInt, "int");
+impl_builtin!(BigUint, "int");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bigint() {
+        assert_eq!(<BigInt as PyStubType>::type_input().name, "int");
+        assert_eq!(
+            <Vec<BigUint> as PyStubType>::type_output().name,
+            "list[int]"
+        );
+    }
+}
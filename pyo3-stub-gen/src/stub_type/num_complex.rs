@@ -0,0 +1,25 @@
+//! Define PyStubType for complex numbers of [num-complex](https://docs.rs/num-complex), enabled by `num-complex` feature
+
+use crate::stub_type::*;
+use ::num_complex::Complex;
+
+/// PyO3 converts `Complex<f32>` and `Complex<f64>` into Python `complex`
+impl<T> PyStubType for Complex<T> {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("complex")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_complex() {
+        assert_eq!(<Complex<f64> as PyStubType>::type_input().name, "complex");
+        assert_eq!(
+            <Option<Complex<f32>> as PyStubType>::type_output().name,
+            "complex | None"
+        );
+    }
+}
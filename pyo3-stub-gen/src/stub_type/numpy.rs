@@ -0,0 +1,112 @@
+//! Define PyStubType for arrays of [rust-numpy](https://github.com/PyO3/rust-numpy), enabled by `numpy` feature
+
+use crate::stub_type::*;
+use ::numpy::{
+    ndarray::Dimension, Complex32, Complex64, Element, PyArray, PyReadonlyArray, PyReadwriteArray,
+    PyUntypedArray,
+};
+
+/// Type of `numpy` module, e.g. `numpy.float64`
+fn numpy(name: &str) -> TypeInfo {
+    TypeInfo::qualified("numpy", name)
+}
+
+/// `numpy.typing.NDArray[dtype]`
+fn nd_array(dtype: TypeInfo) -> TypeInfo {
+    TypeInfo::qualified("numpy.typing", "NDArray").with_args([dtype])
+}
+
+/// Rust element type of arrays corresponding to a NumPy scalar type
+pub trait NumPyScalar: Element {
+    /// NumPy scalar type, e.g. `numpy.float64` for `f64`
+    fn type_() -> TypeInfo;
+}
+
+macro_rules! impl_numpy_scalar {
+    ($ty:ty, $name:expr) => {
+        impl NumPyScalar for $ty {
+            fn type_() -> TypeInfo {
+                numpy($name)
+            }
+        }
+    };
+}
+
+impl_numpy_scalar!(bool, "bool_");
+impl_numpy_scalar!(i8, "int8");
+impl_numpy_scalar!(i16, "int16");
+impl_numpy_scalar!(i32, "int32");
+impl_numpy_scalar!(i64, "int64");
+impl_numpy_scalar!(isize, "intp");
+impl_numpy_scalar!(u8, "uint8");
+impl_numpy_scalar!(u16, "uint16");
+impl_numpy_scalar!(u32, "uint32");
+impl_numpy_scalar!(u64, "uint64");
+impl_numpy_scalar!(usize, "uintp");
+impl_numpy_scalar!(f32, "float32");
+impl_numpy_scalar!(f64, "float64");
+impl_numpy_scalar!(Complex32, "complex64");
+impl_numpy_scalar!(Complex64, "complex128");
+
+/// `numpy.typing.NDArray[T]` for arrays of dynamic dimension,
+/// and `numpy.ndarray[tuple[int, int], numpy.dtype[T]]` for arrays of fixed dimension to annotate the shape.
+impl<T: NumPyScalar, D: Dimension> PyStubType for PyArray<T, D> {
+    fn type_output() -> TypeInfo {
+        match D::NDIM {
+            Some(ndim) => {
+                // Shape of zero-dimensional array is `tuple[()]`
+                let shape = if ndim == 0 {
+                    TypeInfo::builtin("tuple[()]")
+                } else {
+                    TypeInfo::builtin("tuple")
+                        .with_args((0..ndim).map(|_| TypeInfo::builtin("int")))
+                };
+                numpy("ndarray").with_args([shape, numpy("dtype").with_args([T::type_()])])
+            }
+            None => nd_array(T::type_()),
+        }
+    }
+}
+
+impl PyStubType for PyUntypedArray {
+    fn type_output() -> TypeInfo {
+        nd_array(TypeInfo::any())
+    }
+}
+
+impl<T: NumPyScalar, D: Dimension> PyStubType for PyReadonlyArray<'_, T, D> {
+    fn type_output() -> TypeInfo {
+        PyArray::<T, D>::type_output()
+    }
+}
+
+impl<T: NumPyScalar, D: Dimension> PyStubType for PyReadwriteArray<'_, T, D> {
+    fn type_output() -> TypeInfo {
+        PyArray::<T, D>::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::numpy::{PyArray1, PyArrayDyn, PyReadonlyArray2};
+
+    #[test]
+    fn test_array() {
+        assert_eq!(
+            <PyArrayDyn<f32> as PyStubType>::type_output().name,
+            "numpy.typing.NDArray[numpy.float32]"
+        );
+        assert_eq!(
+            <PyArray1<i64> as PyStubType>::type_output().name,
+            "numpy.ndarray[tuple[int], numpy.dtype[numpy.int64]]"
+        );
+        assert_eq!(
+            <PyReadonlyArray2<f64> as PyStubType>::type_input(),
+            TypeInfo {
+                name: "numpy.ndarray[tuple[int, int], numpy.dtype[numpy.float64]]".to_string(),
+                import: [ImportRef::Module("numpy".to_string())].into(),
+            }
+        );
+    }
+}
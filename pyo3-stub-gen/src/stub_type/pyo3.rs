@@ -0,0 +1,182 @@
+use crate::stub_type::*;
+use ::pyo3::{exceptions::*, pyclass::CompareOp, types::*, Py, PyCell, PyClass, PyRef, PyRefMut};
+
+impl PyStubType for PyAny {
+    fn type_output() -> TypeInfo {
+        TypeInfo::any()
+    }
+}
+
+impl<T: PyStubType> PyStubType for Py<T> {
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+impl<T: PyStubType + PyClass> PyStubType for PyCell<T> {
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+impl<T: PyStubType + PyClass> PyStubType for PyRef<'_, T> {
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+impl<T: PyStubType + PyClass<Frozen = ::pyo3::pyclass::boolean_struct::False>> PyStubType
+    for PyRefMut<'_, T>
+{
+    fn type_input() -> TypeInfo {
+        T::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        T::type_output()
+    }
+}
+
+/// `CompareOp` is an argument of `__richcmp__`, which is passed as an integer.
+impl PyStubType for CompareOp {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("int")
+    }
+}
+
+impl_builtin!(PyString, "str");
+impl_builtin!(PyBool, "bool");
+impl_builtin!(PyLong, "int");
+impl_builtin!(PyFloat, "float");
+impl_builtin!(PyComplex, "complex");
+impl_builtin!(PyBytes, "bytes");
+impl_builtin!(PyByteArray, "bytearray");
+impl_builtin!(PySlice, "slice");
+impl_builtin!(PyType, "type");
+
+impl PyStubType for PyList {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("list").with_args([TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PyTuple {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("tuple").with_args([TypeInfo::any(), TypeInfo::builtin("...")])
+    }
+}
+
+impl PyStubType for PyDict {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("dict").with_args([TypeInfo::any(), TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PySet {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("set").with_args([TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PyFrozenSet {
+    fn type_output() -> TypeInfo {
+        TypeInfo::builtin("frozenset").with_args([TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PySequence {
+    fn type_output() -> TypeInfo {
+        TypeInfo::imported("collections.abc", "Sequence").with_args([TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PyMapping {
+    fn type_output() -> TypeInfo {
+        TypeInfo::imported("collections.abc", "Mapping")
+            .with_args([TypeInfo::any(), TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PyIterator {
+    fn type_output() -> TypeInfo {
+        TypeInfo::imported("collections.abc", "Iterator").with_args([TypeInfo::any()])
+    }
+}
+
+impl PyStubType for PyModule {
+    fn type_output() -> TypeInfo {
+        TypeInfo::imported("types", "ModuleType")
+    }
+}
+
+// Built-in exceptions, which may appear as `#[pyclass(extends = PyException)]`
+impl_builtin!(PyBaseException, "BaseException");
+impl_builtin!(PyException, "Exception");
+impl_builtin!(PyStopAsyncIteration, "StopAsyncIteration");
+impl_builtin!(PyStopIteration, "StopIteration");
+impl_builtin!(PyGeneratorExit, "GeneratorExit");
+impl_builtin!(PyArithmeticError, "ArithmeticError");
+impl_builtin!(PyLookupError, "LookupError");
+impl_builtin!(PyAssertionError, "AssertionError");
+impl_builtin!(PyAttributeError, "AttributeError");
+impl_builtin!(PyBufferError, "BufferError");
+impl_builtin!(PyEOFError, "EOFError");
+impl_builtin!(PyFloatingPointError, "FloatingPointError");
+impl_builtin!(PyOSError, "OSError");
+impl_builtin!(PyImportError, "ImportError");
+impl_builtin!(PyModuleNotFoundError, "ModuleNotFoundError");
+impl_builtin!(PyIndexError, "IndexError");
+impl_builtin!(PyKeyError, "KeyError");
+impl_builtin!(PyKeyboardInterrupt, "KeyboardInterrupt");
+impl_builtin!(PyMemoryError, "MemoryError");
+impl_builtin!(PyNameError, "NameError");
+impl_builtin!(PyOverflowError, "OverflowError");
+impl_builtin!(PyRuntimeError, "RuntimeError");
+impl_builtin!(PyRecursionError, "RecursionError");
+impl_builtin!(PyNotImplementedError, "NotImplementedError");
+impl_builtin!(PySyntaxError, "SyntaxError");
+impl_builtin!(PyReferenceError, "ReferenceError");
+impl_builtin!(PySystemError, "SystemError");
+impl_builtin!(PySystemExit, "SystemExit");
+impl_builtin!(PyTypeError, "TypeError");
+impl_builtin!(PyUnboundLocalError, "UnboundLocalError");
+impl_builtin!(PyUnicodeError, "UnicodeError");
+impl_builtin!(PyUnicodeDecodeError, "UnicodeDecodeError");
+impl_builtin!(PyUnicodeEncodeError, "UnicodeEncodeError");
+impl_builtin!(PyUnicodeTranslateError, "UnicodeTranslateError");
+impl_builtin!(PyValueError, "ValueError");
+impl_builtin!(PyZeroDivisionError, "ZeroDivisionError");
+impl_builtin!(PyBlockingIOError, "BlockingIOError");
+impl_builtin!(PyBrokenPipeError, "BrokenPipeError");
+impl_builtin!(PyChildProcessError, "ChildProcessError");
+impl_builtin!(PyConnectionError, "ConnectionError");
+impl_builtin!(PyConnectionAbortedError, "ConnectionAbortedError");
+impl_builtin!(PyConnectionRefusedError, "ConnectionRefusedError");
+impl_builtin!(PyConnectionResetError, "ConnectionResetError");
+impl_builtin!(PyFileExistsError, "FileExistsError");
+impl_builtin!(PyFileNotFoundError, "FileNotFoundError");
+impl_builtin!(PyInterruptedError, "InterruptedError");
+impl_builtin!(PyIsADirectoryError, "IsADirectoryError");
+impl_builtin!(PyNotADirectoryError, "NotADirectoryError");
+impl_builtin!(PyPermissionError, "PermissionError");
+impl_builtin!(PyProcessLookupError, "ProcessLookupError");
+impl_builtin!(PyTimeoutError, "TimeoutError");
+impl_builtin!(PyWarning, "Warning");
+impl_builtin!(PyUserWarning, "UserWarning");
+impl_builtin!(PyDeprecationWarning, "DeprecationWarning");
+impl_builtin!(PyPendingDeprecationWarning, "PendingDeprecationWarning");
+impl_builtin!(PySyntaxWarning, "SyntaxWarning");
+impl_builtin!(PyRuntimeWarning, "RuntimeWarning");
+impl_builtin!(PyFutureWarning, "FutureWarning");
+impl_builtin!(PyImportWarning, "ImportWarning");
+impl_builtin!(PyUnicodeWarning, "UnicodeWarning");
+impl_builtin!(PyBytesWarning, "BytesWarning");
+impl_builtin!(PyResourceWarning, "ResourceWarning");
@@ -0,0 +1,22 @@
+//! Define PyStubType for decimal numbers of [rust_decimal](https://docs.rs/rust_decimal), enabled by `rust_decimal` feature
+
+use crate::stub_type::*;
+use ::rust_decimal::Decimal;
+
+impl_qualified!(Decimal, "decimal", "Decimal");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(
+            <Decimal as PyStubType>::type_input(),
+            TypeInfo {
+                name: "decimal.Decimal".to_string(),
+                import: [ImportRef::Module("decimal".to_string())].into(),
+            }
+        );
+    }
+}
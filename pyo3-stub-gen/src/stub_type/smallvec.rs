@@ -0,0 +1,33 @@
+//! Define PyStubType for [smallvec](https://docs.rs/smallvec), enabled by `smallvec` feature
+
+use crate::stub_type::*;
+use ::smallvec::{Array, SmallVec};
+
+impl<A: Array> PyStubType for SmallVec<A>
+where
+    A::Item: PyStubType,
+{
+    fn type_input() -> TypeInfo {
+        Vec::<A::Item>::type_input()
+    }
+    fn type_output() -> TypeInfo {
+        Vec::<A::Item>::type_output()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_smallvec() {
+        assert_eq!(
+            <SmallVec<[u8; 4]> as PyStubType>::type_input().name,
+            "Sequence[int]"
+        );
+        assert_eq!(
+            <SmallVec<[u8; 4]> as PyStubType>::type_output().name,
+            "list[int]"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Define PyStubType for date and time types of [time](https://docs.rs/time), enabled by `time` feature
+
+use crate::stub_type::*;
+use ::time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+impl_qualified!(Date, "datetime", "date");
+impl_qualified!(Time, "datetime", "time");
+impl_qualified!(PrimitiveDateTime, "datetime", "datetime");
+impl_qualified!(OffsetDateTime, "datetime", "datetime");
+impl_qualified!(Duration, "datetime", "timedelta");
+impl_qualified!(UtcOffset, "datetime", "tzinfo");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_time() {
+        assert_eq!(
+            <OffsetDateTime as PyStubType>::type_output(),
+            TypeInfo {
+                name: "datetime.datetime".to_string(),
+                import: [ImportRef::Module("datetime".to_string())].into(),
+            }
+        );
+        assert_eq!(
+            <Option<Date> as PyStubType>::type_input().name,
+            "datetime.date | None"
+        );
+    }
+}
@@ -21,18 +21,78 @@
 //! This process is done at runtime in [gen_stub](../../gen_stub) executable.
 //!
 
-use pyo3::inspect::types::TypeInfo;
-use std::any::TypeId;
+use crate::stub_type::{PyStubType, TypeInfo};
+use std::{any::TypeId, marker::PhantomData};
 
-/// Work around for `CompareOp` for `__richcmp__` argument,
-/// which does not implements `FromPyObject`
-pub fn compare_op_type_input() -> TypeInfo {
-    use pyo3::FromPyObject;
-    isize::type_input()
+/// Return type of functions and methods without return type, i.e. `None` in Python
+pub fn no_return_type_output() -> TypeInfo {
+    TypeInfo::none()
 }
 
-pub fn no_return_type_output() -> TypeInfo {
-    TypeInfo::NoReturn
+/// Return type of functions returning a Python awaitable resolving to `T`,
+/// declared by `#[gen_stub(awaitable(T))]`
+pub fn awaitable_output<T: PyStubType>() -> TypeInfo {
+    TypeInfo::imported("collections.abc", "Awaitable").with_args([T::type_output()])
+}
+
+/// Enums defined with `#[gen_stub_pyclass_enum]`
+pub trait PyClassEnum: PyStubType {
+    /// Python name of the variant named `variant` in Rust, which may be renamed by `#[pyo3(name = "...")]`
+    fn variant_name(variant: &str) -> Option<&'static str>;
+}
+
+/// Helper for rendering a default value `T::Name` in signature as `T.Name`
+/// only if `T` is [PyClassEnum], and as `...` otherwise.
+///
+/// The method resolution prefers [EnumVariantDefault] implemented for `DefaultValue<T>`
+/// over [OtherDefault] implemented for `&DefaultValue<T>` if it is applicable.
+#[doc(hidden)]
+pub struct DefaultValue<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> DefaultValue<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait EnumVariantDefault {
+    fn variant(&self, name: &str) -> TypeInfo;
+}
+
+impl<T: PyClassEnum> EnumVariantDefault for DefaultValue<T> {
+    fn variant(&self, name: &str) -> TypeInfo {
+        // Not a variant, e.g. an associated constant
+        let Some(name) = T::variant_name(name) else {
+            return TypeInfo::builtin("...");
+        };
+        let ty = T::type_output();
+        TypeInfo {
+            name: format!("{}.{}", ty.name, name),
+            import: ty.import,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait OtherDefault {
+    fn variant(&self, name: &str) -> TypeInfo;
+}
+
+impl<T: ?Sized> OtherDefault for &DefaultValue<T> {
+    fn variant(&self, _name: &str) -> TypeInfo {
+        TypeInfo::builtin("...")
+    }
+}
+
+/// Python signature of a function or method specified by `#[gen_stub(overload(...))]`
+#[derive(Debug, PartialEq)]
+pub struct OverloadInfo {
+    /// Python signature like `def f(x: int) -> int`
+    pub signature: &'static str,
+    /// Modules to be imported as `import {module}` for using types in `signature`
+    pub imports: &'static [&'static str],
 }
 
 /// Info of method argument appears in `#[pymethods]`
@@ -40,6 +100,27 @@ pub fn no_return_type_output() -> TypeInfo {
 pub struct ArgInfo {
     pub name: &'static str,
     pub r#type: fn() -> TypeInfo,
+    /// Trailing `Option<T>` argument without `#[pyo3(signature = (...))]`, which defaults to `None`
+    pub is_optional: bool,
+}
+
+/// Component of the signature specified by `#[pyo3(signature = (...))]`
+///
+/// Names in this signature refer to [ArgInfo] of the same function.
+#[derive(Debug, Clone, Copy)]
+pub enum SignatureArg {
+    /// `a`
+    Ident(&'static str),
+    /// `a = value`, where the value is rendered as a Python expression, or `...` if it cannot be
+    Assign(&'static str, fn() -> TypeInfo),
+    /// `*`, the following arguments are keyword-only
+    Star,
+    /// `/`, the preceding arguments are positional-only
+    Slash,
+    /// `*args`
+    Args(&'static str),
+    /// `**kwargs`
+    Keywords(&'static str),
 }
 
 /// Info of usual method appears in `#[pymethod]`
@@ -48,13 +129,22 @@ pub struct MethodInfo {
     pub name: &'static str,
     pub args: &'static [ArgInfo],
     pub r#return: fn() -> TypeInfo,
-    pub signature: Option<&'static str>,
+    pub signature: Option<&'static [SignatureArg]>,
     pub doc: &'static str,
     pub is_static: bool,
     pub is_class: bool,
+    /// Declared as `async fn`
+    pub is_async: bool,
+    /// Python signatures like `def f(x: int) -> int` specified by `#[gen_stub(overload = "...")]`
+    pub overloads: &'static [OverloadInfo],
+    /// Return type is specified by `#[gen_stub(override_return_type(...))]` or `#[gen_stub(awaitable(T))]`,
+    /// and not fixed by Python protocols of special methods
+    pub is_return_overridden: bool,
 }
 
-/// Info of getter method decorated with `#[getter]` or `#[pyo3(get, set)]` appears in `#[pyclass]`
+/// Info of getter or setter, decorated with `#[getter]`, `#[setter]` or `#[pyo3(get, set)]`
+///
+/// `r#type` is the type returned by the getter, or the type of the value accepted by the setter.
 #[derive(Debug)]
 pub struct MemberInfo {
     pub name: &'static str,
@@ -65,7 +155,7 @@ pub struct MemberInfo {
 #[derive(Debug)]
 pub struct NewInfo {
     pub args: &'static [ArgInfo],
-    pub signature: Option<&'static str>,
+    pub signature: Option<&'static [SignatureArg]>,
 }
 
 /// Info of `#[pymethod]`
@@ -73,12 +163,22 @@ pub struct NewInfo {
 pub struct PyMethodsInfo {
     // The Rust struct type-id of `impl` block where `#[pymethod]` acts on
     pub struct_id: fn() -> TypeId,
+    /// Name of the Rust type, used in error messages
+    pub struct_name: fn() -> &'static str,
     /// Method specified `#[new]` attribute
     pub new: Option<NewInfo>,
     /// Methods decorated with `#[getter]`
     pub getters: &'static [MemberInfo],
+    /// Methods decorated with `#[setter]`
+    pub setters: &'static [MemberInfo],
+    /// Methods decorated with `#[classattr]`, whose return type is the type of the class attribute
+    pub class_attrs: &'static [MemberInfo],
     /// Other usual methods
     pub methods: &'static [MethodInfo],
+    /// Source file of the `impl` block, used to order multiple blocks of a class
+    pub file: &'static str,
+    /// Line of the `impl` block in the source file
+    pub line: u32,
 }
 
 inventory::collect!(PyMethodsInfo);
@@ -94,8 +194,24 @@ pub struct PyClassInfo {
     pub module: Option<&'static str>,
     /// Docstring
     pub doc: &'static str,
-    /// static members by `#[pyo3(get, set)]`
+    /// static members by `#[pyo3(get)]`
     pub members: &'static [MemberInfo],
+    /// static members by `#[pyo3(set)]`
+    pub setters: &'static [MemberInfo],
+    /// Base class specified by `#[pyclass(extends = Base)]`
+    pub base: Option<fn() -> TypeInfo>,
+    /// Whether `#[pyclass(subclass)]` allows to inherit this class in Python
+    pub subclass: bool,
+    /// `#[pyclass(eq)]` generates `__eq__` and `__ne__`
+    pub eq: bool,
+    /// `#[pyclass(ord)]` generates `__lt__`, `__le__`, `__gt__` and `__ge__`
+    pub ord: bool,
+    /// `#[pyclass(hash)]` generates `__hash__`
+    pub hash: bool,
+    /// `#[pyclass(str)]` generates `__str__`
+    pub str: bool,
+    /// `#[pyclass(frozen)]` makes all members read-only
+    pub frozen: bool,
 }
 
 inventory::collect!(PyClassInfo);
@@ -112,11 +228,36 @@ pub struct PyEnumInfo {
     /// Docstring
     pub doc: &'static str,
     /// Variants of enum
-    pub variants: &'static [&'static str],
+    pub variants: &'static [VariantInfo],
 }
 
 inventory::collect!(PyEnumInfo);
 
+/// Shape of enum variant
+///
+/// Enums consisting only of unit variants are exposed as Python `Enum`,
+/// while others are exposed as a base class with a nested subclass for each variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantKind {
+    /// `A`
+    Unit,
+    /// `A(T, U)`, whose fields are exposed as `_0`, `_1`, ...
+    Tuple,
+    /// `A { x: T }`
+    Struct,
+}
+
+/// Info of a variant of `#[pyclass]` enum
+#[derive(Debug)]
+pub struct VariantInfo {
+    pub name: &'static str,
+    pub kind: VariantKind,
+    /// Fields of tuple or struct variant
+    pub fields: &'static [MemberInfo],
+    /// Arguments of the constructor corresponding to `fields`, typed by the input types
+    pub args: &'static [MemberInfo],
+}
+
 /// Info of `#[pyfunction]`
 #[derive(Debug)]
 pub struct PyFunctionInfo {
@@ -124,15 +265,38 @@ pub struct PyFunctionInfo {
     pub args: &'static [ArgInfo],
     pub r#return: fn() -> TypeInfo,
     pub doc: &'static str,
-    pub signature: Option<&'static str>,
+    pub signature: Option<&'static [SignatureArg]>,
     pub module: Option<&'static str>,
+    /// Declared as `async fn`
+    pub is_async: bool,
+    /// Python signatures like `def f(x: int) -> int` specified by `#[gen_stub(overload = "...")]`
+    pub overloads: &'static [OverloadInfo],
 }
 
 inventory::collect!(PyFunctionInfo);
 
+/// Info of exception created by [crate::create_exception]
 #[derive(Debug)]
 pub struct PyErrorInfo {
     pub name: &'static str,
+    /// Module where the exception is defined
+    pub module: &'static str,
+    /// Base exception class, e.g. `ValueError`
+    pub base: fn() -> TypeInfo,
+    pub doc: &'static str,
 }
 
 inventory::collect!(PyErrorInfo);
+
+/// Info of module variable declared by [crate::module_variable]
+#[derive(Debug)]
+pub struct PyVariableInfo {
+    pub name: &'static str,
+    /// Module where the variable is defined
+    pub module: &'static str,
+    pub r#type: fn() -> TypeInfo,
+    /// Whether the variable is annotated as `Final`, i.e. a constant
+    pub is_final: bool,
+}
+
+inventory::collect!(PyVariableInfo);
@@ -0,0 +1,28 @@
+/// Declare a module variable added in `#[pymodule]` for stub file generation
+///
+/// ```
+/// pyo3_stub_gen::module_variable!(my_module, VERSION: Final<&str>);
+/// pyo3_stub_gen::module_variable!(my_module, default_tolerance: f64);
+/// ```
+///
+/// These generate `VERSION: Final[str]` and `default_tolerance: float` in the stub file of `my_module`.
+/// The value itself should be added to the module separately, e.g. by `m.add("VERSION", "1.0")?`.
+#[macro_export]
+macro_rules! module_variable {
+    ($module: expr, $name: ident: Final<$ty: ty>) => {
+        $crate::module_variable!(@submit $module, $name, $ty, true);
+    };
+    ($module: expr, $name: ident: $ty: ty) => {
+        $crate::module_variable!(@submit $module, $name, $ty, false);
+    };
+    (@submit $module: expr, $name: ident, $ty: ty, $is_final: expr) => {
+        $crate::inventory::submit! {
+            $crate::type_info::PyVariableInfo {
+                name: stringify!($name),
+                module: stringify!($module),
+                r#type: <$ty as $crate::PyStubType>::type_output,
+                is_final: $is_final,
+            }
+        }
+    };
+}